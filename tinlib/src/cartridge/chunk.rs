@@ -1,6 +1,6 @@
 //! Chunk implementation and manipulation.\
 use std::convert::TryFrom;
-use std::io::{Read, Write};
+use std::io::{self, ErrorKind, Read, Write};
 use std::result::Result as StdResult;
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
@@ -12,9 +12,14 @@ use crate::cartridge::error::{CartridgeError, Result};
 const END_CHUNK_VALID_SIZE: [usize; 1] = [0];
 const COVER_CHUNK_VALID_SIZES: [usize; 2] = [0, 245760];
 const FONT_CHUNK_VALID_SIZES: [usize; 2] = [0, 16384];
+// Palette chunks hold 0, 4, 8 or 16 RGB colors, 3 bytes each.
 const PALETTE_CHUNK_VALID_SIZES: [usize; 4] = [0, 12, 24, 48];
 const CODE_CHUNK_MAX_SIZE: usize = 131072;
 const MAP_CHUNK_MAX_SIZE: usize = 122880;
+const LANGUAGE_CHUNK_MAX_SIZE: usize = 255;
+
+/// The chunk header size in bytes.
+pub const CHUNK_HEADER_SIZE: usize = 5;
 
 /// The Chunk type.
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -31,6 +36,8 @@ pub enum ChunkType {
     Palette = 4,
     /// Map data.
     Map = 5,
+    /// Code language data.
+    Language = 6,
 }
 
 impl TryFrom<u8> for ChunkType {
@@ -44,6 +51,7 @@ impl TryFrom<u8> for ChunkType {
             3 => Ok(ChunkType::Font),
             4 => Ok(ChunkType::Palette),
             5 => Ok(ChunkType::Map),
+            6 => Ok(ChunkType::Language),
             _ => Err(CartridgeError::new_invalid_chunk_type(value)),
         }
     }
@@ -59,22 +67,41 @@ pub struct ChunkHeader {
 }
 
 impl ChunkHeader {
-    /// Creates a ChunkHeader with the type and data provided.
-    pub fn new(chunk_type: ChunkType, size: usize) -> Self {
-        Self {
-            chunk_type,
-            size: size as u32,
+    /// Creates a ChunkHeader with the type and data size provided.
+    ///
+    /// Returns an error if the size doesn't fit in the 32 bits header field.
+    pub fn new(chunk_type: ChunkType, size: usize) -> Result<Self> {
+        let size = u32::try_from(size).map_err(|_| {
+            CartridgeError::new_invalid_chunk_max_size(chunk_type, size, u32::MAX as usize)
+        })?;
+
+        Ok(Self { chunk_type, size })
+    }
+
+    /// Creates a ChunkHeader from a Reader, or returns `None` if the Reader
+    /// ends before the first header byte.
+    ///
+    /// A Reader ending in the middle of the header is still an `Io` error.
+    pub fn from_reader_or_end<R: Read>(reader: &mut R) -> Result<Option<ChunkHeader>> {
+        let mut chunk_type = [0u8; 1];
+        loop {
+            match reader.read(&mut chunk_type) {
+                Ok(0) => return Ok(None),
+                Ok(_) => break,
+                Err(error) if error.kind() == ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error.into()),
+            }
         }
-    }
-
-    /// Creates a ChunkHeader from the data read from a Reader.
-    pub fn from_reader<R: Read>(reader: &mut R) -> Result<ChunkHeader> {
-        let chunk_type = reader.read_u8()?;
-        let chunk_type = ChunkType::try_from(chunk_type)?;
+        let chunk_type = ChunkType::try_from(chunk_type[0])?;
 
         let size = reader.read_u32::<LittleEndian>()?;
 
-        Ok(ChunkHeader { chunk_type, size })
+        Ok(Some(ChunkHeader { chunk_type, size }))
+    }
+
+    /// Returns the data size.
+    pub fn size(&self) -> usize {
+        self.size as usize
     }
 
     // Saves the ChunkHeader data into a Writer.
@@ -103,13 +130,6 @@ pub struct Chunk {
 }
 
 impl Chunk {
-    /// Creates a Chunk with the type and data provided.
-    pub fn new(chunk_type: ChunkType, data: Vec<u8>) -> Self {
-        let header = ChunkHeader::new(chunk_type, data.len());
-
-        Self { header, data }
-    }
-
     pub fn chunk_type(&self) -> ChunkType {
         self.header.chunk_type
     }
@@ -118,13 +138,22 @@ impl Chunk {
         &self.data
     }
 
-    /// Creates a Chunk from the data read from a Reader.
-    pub fn from_reader<R: Read>(reader: &mut R) -> Result<Chunk> {
-        let header = ChunkHeader::from_reader(reader)?;
+    /// Consumes the Chunk, returning its data.
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Creates a Chunk from an already read header and the data read from a Reader.
+    ///
+    /// The header size is validated before reading, and the data buffer only
+    /// grows as bytes are read, so a bogus size can't allocate up front.
+    pub fn from_header_and_reader<R: Read>(header: ChunkHeader, reader: &mut R) -> Result<Chunk> {
+        Self::validate_size(header.chunk_type, header.size())?;
 
-        let mut data = Vec::with_capacity(header.size as usize);
-        for _ in 0..header.size {
-            data.push(reader.read_u8()?);
+        let mut data = Vec::new();
+        reader.take(header.size as u64).read_to_end(&mut data)?;
+        if data.len() != header.size() {
+            return Err(io::Error::from(ErrorKind::UnexpectedEof).into());
         }
 
         let chunk = Chunk { header, data };
@@ -155,21 +184,27 @@ impl Chunk {
             ));
         }
 
-        match self.chunk_type() {
-            ChunkType::End => self.validate_end(),
-            ChunkType::Cover => self.validate_cover(),
-            ChunkType::Code => self.validate_code(),
-            ChunkType::Font => self.validate_font(),
-            ChunkType::Palette => self.validate_palette(),
-            ChunkType::Map => self.validate_map(),
+        Self::validate_size(self.chunk_type(), self.data.len())
+    }
+
+    /// Checks if a data size is valid for a chunk type.
+    pub(crate) fn validate_size(chunk_type: ChunkType, size: usize) -> Result<()> {
+        match chunk_type {
+            ChunkType::End => Self::validate_end(size),
+            ChunkType::Cover => Self::validate_cover(size),
+            ChunkType::Code => Self::validate_code(size),
+            ChunkType::Font => Self::validate_font(size),
+            ChunkType::Palette => Self::validate_palette(size),
+            ChunkType::Map => Self::validate_map(size),
+            ChunkType::Language => Self::validate_language(size),
         }
     }
 
-    fn validate_end(&self) -> Result<()> {
-        if !END_CHUNK_VALID_SIZE.contains(&self.data.len()) {
+    fn validate_end(size: usize) -> Result<()> {
+        if !END_CHUNK_VALID_SIZE.contains(&size) {
             return Err(CartridgeError::new_invalid_chunk_size(
-                self.header.chunk_type,
-                self.data.len(),
+                ChunkType::End,
+                size,
                 END_CHUNK_VALID_SIZE.to_vec(),
             ));
         }
@@ -177,11 +212,11 @@ impl Chunk {
         Ok(())
     }
 
-    fn validate_cover(&self) -> Result<()> {
-        if !COVER_CHUNK_VALID_SIZES.contains(&self.data.len()) {
+    fn validate_cover(size: usize) -> Result<()> {
+        if !COVER_CHUNK_VALID_SIZES.contains(&size) {
             return Err(CartridgeError::new_invalid_chunk_size(
-                self.header.chunk_type,
-                self.data.len(),
+                ChunkType::Cover,
+                size,
                 COVER_CHUNK_VALID_SIZES.to_vec(),
             ));
         }
@@ -189,11 +224,11 @@ impl Chunk {
         Ok(())
     }
 
-    fn validate_code(&self) -> Result<()> {
-        if self.data.len() > CODE_CHUNK_MAX_SIZE {
+    fn validate_code(size: usize) -> Result<()> {
+        if size > CODE_CHUNK_MAX_SIZE {
             return Err(CartridgeError::new_invalid_chunk_max_size(
-                self.header.chunk_type,
-                self.data.len(),
+                ChunkType::Code,
+                size,
                 CODE_CHUNK_MAX_SIZE,
             ));
         }
@@ -201,11 +236,11 @@ impl Chunk {
         Ok(())
     }
 
-    fn validate_font(&self) -> Result<()> {
-        if !FONT_CHUNK_VALID_SIZES.contains(&self.data.len()) {
+    fn validate_font(size: usize) -> Result<()> {
+        if !FONT_CHUNK_VALID_SIZES.contains(&size) {
             return Err(CartridgeError::new_invalid_chunk_size(
-                self.header.chunk_type,
-                self.data.len(),
+                ChunkType::Font,
+                size,
                 FONT_CHUNK_VALID_SIZES.to_vec(),
             ));
         }
@@ -213,11 +248,11 @@ impl Chunk {
         Ok(())
     }
 
-    fn validate_palette(&self) -> Result<()> {
-        if !PALETTE_CHUNK_VALID_SIZES.contains(&self.data.len()) {
+    fn validate_palette(size: usize) -> Result<()> {
+        if !PALETTE_CHUNK_VALID_SIZES.contains(&size) {
             return Err(CartridgeError::new_invalid_chunk_size(
-                self.header.chunk_type,
-                self.data.len(),
+                ChunkType::Palette,
+                size,
                 PALETTE_CHUNK_VALID_SIZES.to_vec(),
             ));
         }
@@ -225,17 +260,29 @@ impl Chunk {
         Ok(())
     }
 
-    fn validate_map(&self) -> Result<()> {
-        if self.data.len() > MAP_CHUNK_MAX_SIZE {
+    fn validate_map(size: usize) -> Result<()> {
+        if size > MAP_CHUNK_MAX_SIZE {
             return Err(CartridgeError::new_invalid_chunk_max_size(
-                self.header.chunk_type,
-                self.data.len(),
+                ChunkType::Map,
+                size,
                 MAP_CHUNK_MAX_SIZE,
             ));
         }
 
         Ok(())
     }
+
+    fn validate_language(size: usize) -> Result<()> {
+        if size > LANGUAGE_CHUNK_MAX_SIZE {
+            return Err(CartridgeError::new_invalid_chunk_max_size(
+                ChunkType::Language,
+                size,
+                LANGUAGE_CHUNK_MAX_SIZE,
+            ));
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for Chunk {
@@ -255,6 +302,12 @@ mod test {
 
     use super::*;
 
+    fn chunk_from_reader<R: Read>(reader: &mut R) -> Result<Chunk> {
+        let header = ChunkHeader::from_reader_or_end(reader)?.expect("a chunk header");
+
+        Chunk::from_header_and_reader(header, reader)
+    }
+
     #[test]
     fn test_chunktype_tryfrom() {
         let data = [
@@ -264,6 +317,7 @@ mod test {
             (3, ChunkType::Font),
             (4, ChunkType::Palette),
             (5, ChunkType::Map),
+            (6, ChunkType::Language),
         ];
 
         for (value, expected) in data.iter() {
@@ -287,39 +341,68 @@ mod test {
     }
 
     #[test]
-    fn test_chunkheader_from_reader() {
+    fn test_chunkheader_from_reader_or_end() {
         let mut reader = Cursor::new(vec![5, 0, 240, 0, 0]);
         let expected = ChunkHeader {
             chunk_type: ChunkType::Map,
             size: 61440,
         };
 
-        let result = ChunkHeader::from_reader(&mut reader);
+        let result = ChunkHeader::from_reader_or_end(&mut reader);
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), expected);
+        assert_eq!(result.unwrap(), Some(expected));
     }
 
     #[test]
-    fn test_chunkheader_from_reader_invalid_chunk_type() {
-        let mut reader = Cursor::new(vec![6, 0, 240, 0, 0]);
+    fn test_chunkheader_from_reader_or_end_invalid_chunk_type() {
+        let mut reader = Cursor::new(vec![7, 0, 240, 0, 0]);
 
-        let result = ChunkHeader::from_reader(&mut reader);
+        let result = ChunkHeader::from_reader_or_end(&mut reader);
         assert!(result.is_err());
         assert_matches!(
             result.unwrap_err(),
-            CartridgeError::InvalidChunkType(v) if v == 6
+            CartridgeError::InvalidChunkType(v) if v == 7
         );
     }
 
     #[test]
-    fn test_chunkheader_from_reader_invalid_data() {
-        let mut reader = Cursor::new(vec![5, 0]);
+    fn test_chunkheader_from_reader_or_end_at_end() {
+        let mut reader = Cursor::new(vec![]);
+
+        let result = ChunkHeader::from_reader_or_end(&mut reader);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), None);
+    }
 
-        let result = ChunkHeader::from_reader(&mut reader);
+    #[test]
+    fn test_chunkheader_from_reader_or_end_partial() {
+        let mut reader = Cursor::new(vec![2, 6]);
+
+        let result = ChunkHeader::from_reader_or_end(&mut reader);
         assert!(result.is_err());
         assert_matches!(result.unwrap_err(), CartridgeError::Io(_));
     }
 
+    #[test]
+    fn test_chunkheader_size() {
+        let chunk_header = ChunkHeader::new(ChunkType::Map, 61440).unwrap();
+
+        assert_eq!(chunk_header.size(), 61440);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    fn test_chunkheader_new_size_too_big() {
+        let size = u32::MAX as usize + 1;
+
+        let result = ChunkHeader::new(ChunkType::Code, size);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CartridgeError::InvalidChunkMaxSize(t, s, m) if t == ChunkType::Code && s == size && m == u32::MAX as usize
+        );
+    }
+
     #[test]
     fn test_chunkheader_save() {
         let chunk_header = ChunkHeader {
@@ -372,27 +455,42 @@ mod test {
             data: vec![0, 0, 0, 86, 86, 86, 172, 172, 172, 255, 255, 255],
         };
 
-        let result = Chunk::from_reader(&mut reader);
-        println!("{:?}", result);
+        let result = chunk_from_reader(&mut reader);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), expected);
     }
 
+    #[test]
+    fn test_chunk_from_header_and_reader() {
+        let header = ChunkHeader::new(ChunkType::Code, 2).unwrap();
+        let mut reader = Cursor::new(vec![104, 105]);
+
+        let result = Chunk::from_header_and_reader(header, &mut reader);
+        assert!(result.is_ok());
+        assert_eq!(
+            result.unwrap(),
+            Chunk {
+                header: ChunkHeader::new(ChunkType::Code, 2).unwrap(),
+                data: vec![104, 105],
+            }
+        );
+    }
+
     #[test]
     fn test_header_from_reader_invalid_chunk_type() {
         let mut reader = Cursor::new(vec![
             // header
-            6, // type
+            7, // type
             12, 0, 0, 0, // size
             // data
             0, 0, 0, 86, 86, 86, 172, 172, 172, 255, 255, 255,
         ]);
 
-        let result = Chunk::from_reader(&mut reader);
+        let result = chunk_from_reader(&mut reader);
         assert!(result.is_err());
         assert_matches!(
             result.unwrap_err(),
-            CartridgeError::InvalidChunkType(v) if v == 6
+            CartridgeError::InvalidChunkType(v) if v == 7
         );
     }
 
@@ -406,11 +504,28 @@ mod test {
             0,
         ]);
 
-        let result = Chunk::from_reader(&mut reader);
+        let result = chunk_from_reader(&mut reader);
         assert!(result.is_err());
         assert_matches!(result.unwrap_err(), CartridgeError::Io(_));
     }
 
+    #[test]
+    fn test_chunk_from_header_and_reader_invalid_size() {
+        let header = ChunkHeader {
+            chunk_type: ChunkType::Code,
+            size: u32::MAX,
+        };
+        let mut reader = Cursor::new(vec![]);
+
+        let result = Chunk::from_header_and_reader(header, &mut reader);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CartridgeError::InvalidChunkMaxSize(t, s, m) if t == ChunkType::Code && s == u32::MAX as usize && m == CODE_CHUNK_MAX_SIZE
+        );
+        assert_eq!(reader.position(), 0);
+    }
+
     #[test]
     fn test_chunk_save() {
         let chunk = Chunk {
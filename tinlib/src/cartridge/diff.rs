@@ -0,0 +1,132 @@
+//! CartridgeDiff implementation and manipulation.
+use crate::cartridge::Cartridge;
+
+/// A summary of the sections that differ between two cartridges.
+///
+/// Like `Cartridge`'s `PartialEq`, it ignores the empty sections, as they
+/// only change how sections without data are saved, not the data itself.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CartridgeDiff {
+    /// If the game version changed.
+    pub version_changed: bool,
+    /// If the name changed.
+    pub name_changed: bool,
+    /// If the description changed.
+    pub desc_changed: bool,
+    /// If the author changed.
+    pub author_changed: bool,
+    /// If the cover changed.
+    pub cover_changed: bool,
+    /// If the font changed.
+    pub font_changed: bool,
+    /// If the palette changed.
+    pub palette_changed: bool,
+    /// If the map changed.
+    pub map_changed: bool,
+    /// If the code changed.
+    pub code_changed: bool,
+    /// Number of code lines that differ, compared line by line.
+    pub code_lines_changed: usize,
+    /// If the code language changed.
+    pub code_language_changed: bool,
+}
+
+impl CartridgeDiff {
+    /// Creates a CartridgeDiff comparing two cartridges.
+    pub fn new(old: &Cartridge, new: &Cartridge) -> Self {
+        Self {
+            version_changed: old.version != new.version,
+            name_changed: old.name != new.name,
+            desc_changed: old.desc != new.desc,
+            author_changed: old.author != new.author,
+            cover_changed: old.cover != new.cover,
+            font_changed: old.font != new.font,
+            palette_changed: old.palette != new.palette,
+            map_changed: old.map != new.map,
+            code_changed: old.code != new.code,
+            code_lines_changed: count_changed_lines(&old.code, &new.code),
+            code_language_changed: old.code_language != new.code_language,
+        }
+    }
+
+    /// Returns if any section changed.
+    pub fn has_changes(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+fn count_changed_lines(old: &str, new: &str) -> usize {
+    let mut old_lines = old.lines();
+    let mut new_lines = new.lines();
+    let mut count = 0;
+
+    loop {
+        match (old_lines.next(), new_lines.next()) {
+            (None, None) => break,
+            (a, b) if a != b => count += 1,
+            _ => {}
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+
+    #[test]
+    fn test_cartridgediff_new_no_changes() {
+        let cart = Cartridge::default();
+
+        let diff = CartridgeDiff::new(&cart, &cart.clone());
+
+        assert_eq!(diff, CartridgeDiff::default());
+        assert!(!diff.has_changes());
+    }
+
+    #[test]
+    fn test_cartridgediff_new_code_changes() {
+        let old = Cartridge {
+            code: "def main:\n    pass\n".to_string(),
+            ..Default::default()
+        };
+        let new = Cartridge {
+            code: "def main:\n    run()\n    pass\n".to_string(),
+            ..Default::default()
+        };
+
+        let diff = CartridgeDiff::new(&old, &new);
+
+        assert!(diff.has_changes());
+        assert!(diff.code_changed);
+        assert_eq!(diff.code_lines_changed, 2);
+        assert_eq!(
+            diff,
+            CartridgeDiff {
+                code_changed: true,
+                code_lines_changed: 2,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_cartridgediff_new_section_changes() {
+        let old = Cartridge::default();
+        let new = Cartridge {
+            name: "name".to_string(),
+            palette: vec![0; 12],
+            ..Default::default()
+        };
+
+        let diff = CartridgeDiff::new(&old, &new);
+
+        assert!(diff.name_changed);
+        assert!(diff.palette_changed);
+        assert!(!diff.code_changed);
+        assert!(!diff.font_changed);
+        assert!(!diff.map_changed);
+        assert!(!diff.cover_changed);
+    }
+}
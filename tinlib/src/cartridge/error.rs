@@ -6,6 +6,7 @@ use std::string::FromUtf8Error;
 use thiserror::Error;
 
 use crate::cartridge::chunk::ChunkType;
+use crate::common::CommonError;
 
 /// Cartridge errors.
 #[derive(Error, Debug)]
@@ -22,6 +23,25 @@ pub enum CartridgeError {
     /// Error to represent mismatched chunk sizes.
     #[error("mismatched chunk header size {1} and data sizes {2} for type {0:?}")]
     MismatchedChunkSizes(ChunkType, usize, usize),
+    /// Error to represent fields too long to be saved.
+    #[error("field {0} has {1} bytes, max expected: {2}")]
+    FieldTooLong(&'static str, usize, usize),
+    /// Error to represent cartridges bigger than the allowed size.
+    #[error("cartridge exceeds the max size of {0} bytes")]
+    TooLarge(usize),
+    /// Error to represent a cartridge data ending before the End chunk.
+    #[error("cartridge data ended without an end chunk")]
+    MissingEndChunk,
+    /// Error to represent data after the End chunk, with its size in bytes
+    /// up to the counted limit.
+    #[error("{0} bytes of trailing data after the end chunk")]
+    TrailingData(usize),
+    /// Error to represent cartridge file versions without a reader.
+    #[error("unsupported cartridge file version {0}")]
+    UnsupportedVersion(u8),
+    /// Error to wrap `CommonError`s from decoding the cartridge data.
+    #[error("common error")]
+    Common(#[from] CommonError),
     /// Error to wrap an invalid conversion to UTF8.
     #[error("UFT8 conversion error")]
     FromUtf8(#[from] FromUtf8Error),
@@ -62,6 +82,31 @@ impl CartridgeError {
     ) -> Self {
         Self::MismatchedChunkSizes(chunk_type, header_size, data_size)
     }
+
+    /// Creates a `FieldTooLong` error.
+    pub fn new_field_too_long(field: &'static str, value: usize, expected: usize) -> Self {
+        Self::FieldTooLong(field, value, expected)
+    }
+
+    /// Creates a `TooLarge` error.
+    pub fn new_too_large(max_size: usize) -> Self {
+        Self::TooLarge(max_size)
+    }
+
+    /// Creates a `MissingEndChunk` error.
+    pub fn new_missing_end_chunk() -> Self {
+        Self::MissingEndChunk
+    }
+
+    /// Creates a `TrailingData` error.
+    pub fn new_trailing_data(size: usize) -> Self {
+        Self::TrailingData(size)
+    }
+
+    /// Creates a `UnsupportedVersion` error.
+    pub fn new_unsupported_version(version: u8) -> Self {
+        Self::UnsupportedVersion(version)
+    }
 }
 
 pub type Result<T> = StdResult<T, CartridgeError>;
@@ -125,4 +170,52 @@ mod test_super {
             CartridgeError::MismatchedChunkSizes(ct, h, d) if ct == chunk_type && h == header_size && d == data_size
         );
     }
+
+    #[test]
+    fn test_cartridgeerror_new_field_too_long() {
+        let field = "name";
+        let value = 300usize;
+        let expected = 255usize;
+
+        let error = CartridgeError::new_field_too_long(field, value, expected);
+
+        assert_matches!(
+            error,
+            CartridgeError::FieldTooLong(f, v, e) if f == field && v == value && e == expected
+        );
+    }
+
+    #[test]
+    fn test_cartridgeerror_new_too_large() {
+        let max_size = 1024usize;
+
+        let error = CartridgeError::new_too_large(max_size);
+
+        assert_matches!(error, CartridgeError::TooLarge(m) if m == max_size);
+    }
+
+    #[test]
+    fn test_cartridgeerror_new_missing_end_chunk() {
+        let error = CartridgeError::new_missing_end_chunk();
+
+        assert_matches!(error, CartridgeError::MissingEndChunk);
+    }
+
+    #[test]
+    fn test_cartridgeerror_new_trailing_data() {
+        let size = 3usize;
+
+        let error = CartridgeError::new_trailing_data(size);
+
+        assert_matches!(error, CartridgeError::TrailingData(s) if s == size);
+    }
+
+    #[test]
+    fn test_cartridgeerror_new_unsupported_version() {
+        let version = 99u8;
+
+        let error = CartridgeError::new_unsupported_version(version);
+
+        assert_matches!(error, CartridgeError::UnsupportedVersion(v) if v == version);
+    }
 }
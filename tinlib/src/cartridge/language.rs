@@ -0,0 +1,61 @@
+//! CodeLanguage implementation and manipulation.
+
+/// The name stored for Lua code.
+const LUA_NAME: &str = "lua";
+/// The name stored for Python code.
+const PYTHON_NAME: &str = "python";
+
+/// The language of a cartridge code.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CodeLanguage {
+    /// Lua code.
+    Lua,
+    /// Python code.
+    Python,
+    /// Code in any other language, by name.
+    Custom(String),
+}
+
+impl CodeLanguage {
+    /// Creates a CodeLanguage from its stored name.
+    ///
+    /// Known names are matched exactly, any other name is `Custom`.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            LUA_NAME => CodeLanguage::Lua,
+            PYTHON_NAME => CodeLanguage::Python,
+            _ => CodeLanguage::Custom(name.to_string()),
+        }
+    }
+
+    /// Returns the name stored for the language.
+    pub fn name(&self) -> &str {
+        match self {
+            CodeLanguage::Lua => LUA_NAME,
+            CodeLanguage::Python => PYTHON_NAME,
+            CodeLanguage::Custom(name) => name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+
+    #[test]
+    fn test_codelanguage_from_name() {
+        assert_eq!(CodeLanguage::from_name("lua"), CodeLanguage::Lua);
+        assert_eq!(CodeLanguage::from_name("python"), CodeLanguage::Python);
+        assert_eq!(
+            CodeLanguage::from_name("fennel"),
+            CodeLanguage::Custom("fennel".to_string())
+        );
+    }
+
+    #[test]
+    fn test_codelanguage_name() {
+        assert_eq!(CodeLanguage::Lua.name(), "lua");
+        assert_eq!(CodeLanguage::Python.name(), "python");
+        assert_eq!(CodeLanguage::Custom("fennel".to_string()).name(), "fennel");
+    }
+}
@@ -1,17 +1,36 @@
 //! Cartridge utilities.
 mod chunk;
+mod diff;
 mod error;
+mod language;
+mod section;
+mod writer;
 
+pub use crate::cartridge::chunk::ChunkType;
+pub use crate::cartridge::diff::CartridgeDiff;
 pub use crate::cartridge::error::{CartridgeError, Result};
+pub use crate::cartridge::language::CodeLanguage;
+pub use crate::cartridge::section::Section;
+pub use crate::cartridge::writer::CartridgeWriter;
 
-use std::io::{Read, Write};
+use std::collections::BTreeSet;
+use std::io::{self, Read, Write};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
-use crate::cartridge::chunk::{Chunk, ChunkType};
+use crate::cartridge::chunk::{Chunk, ChunkHeader, CHUNK_HEADER_SIZE};
+use crate::common::Coord;
+use crate::graphic::{Font, Palette, CARTRIDGE_BYTES_PER_COLOR, GLYPH_BYTES, GLYPH_WIDTH};
+use crate::machine::Screen;
+use crate::map::MAP_WIDTH;
 
+/// The cartridge header size in bytes.
+const CARTRIDGE_HEADER_SIZE: usize = 5;
 /// The default cartridge file version.
-const DEFAULT_CART_FILE_VERSION: u8 = 1;
+///
+/// Version 2 stores glyph pixels row by row, version 1 stored them column
+/// by column.
+const DEFAULT_CART_FILE_VERSION: u8 = 2;
 /// The default name size.
 const DEFAULT_NAME_SIZE: u8 = 64;
 /// The default description size.
@@ -20,6 +39,14 @@ const DEFAULT_DESC_SIZE: u16 = 512;
 const DEFAULT_AUTHOR_SIZE: u8 = 64;
 /// The default game version.
 const DEFAULT_VERSION: u8 = 1;
+/// The max name size.
+const MAX_NAME_SIZE: usize = u8::MAX as usize;
+/// The max description size.
+const MAX_DESC_SIZE: usize = u16::MAX as usize;
+/// The max author name size.
+const MAX_AUTHOR_SIZE: usize = u8::MAX as usize;
+/// The max number of trailing bytes counted by strict reads.
+const MAX_TRAILING_DATA: u64 = 1024;
 
 /// The cartridge header.
 #[derive(Debug, Clone, PartialEq)]
@@ -33,7 +60,7 @@ struct CartridgeHeader {
 impl CartridgeHeader {
     /// Creates a CartridgeHeader from the data read from a Reader.
     pub fn from_reader<R: Read>(reader: &mut R) -> Result<CartridgeHeader> {
-        let cart_version = reader.read_u8()?; // TODO validate the version
+        let cart_version = reader.read_u8()?;
         let name_size = reader.read_u8()?;
         let desc_size = reader.read_u16::<LittleEndian>()?;
         let author_size = reader.read_u8()?;
@@ -68,43 +95,299 @@ impl Default for CartridgeHeader {
     }
 }
 
+/// Options to read a cartridge.
+#[derive(Debug, Clone, Copy, Default)]
+struct ReadOptions {
+    /// Max number of bytes to read, if any.
+    max_total_bytes: Option<usize>,
+    /// If invalid UTF-8 in strings is replaced instead of failing.
+    lossy: bool,
+    /// If data after the End chunk is an error instead of ignored.
+    strict: bool,
+    /// If chunks after the End chunk are read too, until the data ends.
+    multi: bool,
+}
+
+impl ReadOptions {
+    /// Converts bytes read into a String.
+    fn decode_string(&self, bytes: Vec<u8>) -> Result<String> {
+        if self.lossy {
+            return Ok(String::from_utf8_lossy(&bytes).into_owned());
+        }
+
+        Ok(String::from_utf8(bytes)?)
+    }
+}
+
+/// Tracks the number of bytes read against the max allowed by ReadOptions.
+struct ReadBudget {
+    consumed: usize,
+    max: Option<usize>,
+}
+
+impl ReadBudget {
+    fn new(options: &ReadOptions) -> Self {
+        Self {
+            consumed: 0,
+            max: options.max_total_bytes,
+        }
+    }
+
+    /// Reserves bytes before reading them, failing if the max is exceeded.
+    fn reserve(&mut self, bytes: usize) -> Result<()> {
+        self.consumed = self.consumed.saturating_add(bytes);
+
+        match self.max {
+            Some(max) if self.consumed > max => Err(CartridgeError::new_too_large(max)),
+            _ => Ok(()),
+        }
+    }
+}
+
 /// The cartridge data.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct Cartridge {
     pub version: u8,
     pub name: String,
     pub desc: String,
     pub author: String,
+    /// The cover image, with one palette index per screen pixel in row-major
+    /// order, or empty if the cartridge has no cover.
     pub cover: Vec<u8>,
     pub font: Vec<u8>,
     pub palette: Vec<u8>,
+    /// The map, with a glyph index and a palette index per tile in row-major
+    /// order.
     pub map: Vec<u8>,
     pub code: String,
+    /// The code language, if specified.
+    ///
+    /// Stored in an optional chunk, so cartridges without it keep loading.
+    /// An empty `Custom` name is saved and loaded as no language.
+    code_language: Option<CodeLanguage>,
+    /// The sections without data that are still saved, as zero-size chunks.
+    ///
+    /// Filled when loading a cartridge with zero-size chunks, so they aren't
+    /// lost when saving it again.
+    empty_sections: BTreeSet<Section>,
 }
 
 impl Cartridge {
+    /// Creates a Cartridge with a name and an author, and default values for the rest.
+    pub fn new(name: impl Into<String>, author: impl Into<String>) -> Result<Cartridge> {
+        let name = name.into();
+        validate_field_size("name", &name, MAX_NAME_SIZE)?;
+
+        let author = author.into();
+        validate_field_size("author", &author, MAX_AUTHOR_SIZE)?;
+
+        Ok(Cartridge {
+            name,
+            author,
+            ..Default::default()
+        })
+    }
+
     pub fn from_reader<R: Read>(reader: &mut R) -> Result<Cartridge> {
-        let mut cart = Cartridge::default();
+        Self::read(reader, ReadOptions::default())
+    }
+
+    /// Creates a Cartridge from a Reader, replacing invalid UTF-8 in the
+    /// name, description, author, code and code language with `U+FFFD`.
+    ///
+    /// Partially corrupt cartridges still load, while `from_reader` fails.
+    pub fn from_reader_lossy<R: Read>(reader: &mut R) -> Result<Cartridge> {
+        let options = ReadOptions {
+            lossy: true,
+            ..Default::default()
+        };
+
+        Self::read(reader, options)
+    }
+
+    /// Creates a Cartridge from a Reader, failing if there is any data after
+    /// the End chunk.
+    ///
+    /// `from_reader` ignores the data after the End chunk. The trailing data
+    /// is counted up to 1024 bytes, so a reader that never ends still fails.
+    pub fn from_reader_strict<R: Read>(reader: &mut R) -> Result<Cartridge> {
+        let options = ReadOptions {
+            strict: true,
+            ..Default::default()
+        };
+
+        Self::read(reader, options)
+    }
+
+    /// Creates a Cartridge from a Reader, reading chunks after each End chunk
+    /// until the data ends.
+    ///
+    /// Later chunks override earlier ones of the same type, so patches can be
+    /// appended to a cartridge.
+    pub fn from_reader_multi<R: Read>(reader: &mut R) -> Result<Cartridge> {
+        let options = ReadOptions {
+            multi: true,
+            ..Default::default()
+        };
+
+        Self::read(reader, options)
+    }
+
+    /// Creates a Cartridge from bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Cartridge> {
+        let mut reader = bytes;
+
+        Self::from_reader(&mut reader)
+    }
+
+    /// Creates a Cartridge from a Reader, failing once more than
+    /// `max_total_bytes` would be read.
+    ///
+    /// Chunk sizes are checked before their data is read, so a malicious
+    /// cartridge can't exhaust the memory.
+    pub fn from_reader_limited<R: Read>(
+        reader: &mut R,
+        max_total_bytes: usize,
+    ) -> Result<Cartridge> {
+        let options = ReadOptions {
+            max_total_bytes: Some(max_total_bytes),
+            ..Default::default()
+        };
+
+        Self::read(reader, options)
+    }
+
+    /// Returns an iterator reading the chunks of a cartridge from a Reader.
+    ///
+    /// The header and metadata are skipped, and each chunk is read only when
+    /// requested, until the End chunk. The End chunk itself is not returned.
+    pub fn chunks<R: Read>(reader: R) -> CartridgeChunks<R> {
+        CartridgeChunks::new(reader)
+    }
+
+    /// Creates a Cartridge from a Reader, also returning the number of bytes
+    /// read.
+    ///
+    /// The reader stops right after the End chunk, so the count is the
+    /// serialized cartridge lenght and can be used to skip past a cartridge
+    /// embedded in a larger container.
+    pub fn from_reader_counted<R: Read>(reader: &mut R) -> Result<(Cartridge, usize)> {
+        Self::read_counted(reader, ReadOptions::default())
+    }
+
+    fn read<R: Read>(reader: &mut R, options: ReadOptions) -> Result<Cartridge> {
+        Self::read_counted(reader, options).map(|(cart, _)| cart)
+    }
+
+    fn read_counted<R: Read>(reader: &mut R, options: ReadOptions) -> Result<(Cartridge, usize)> {
+        let mut budget = ReadBudget::new(&options);
+
+        budget.reserve(CARTRIDGE_HEADER_SIZE)?;
         let header = CartridgeHeader::from_reader(reader)?;
 
+        let cart = Self::migrate(header.cart_version, &header, reader, &options, &mut budget)?;
+
+        if options.strict {
+            let trailing = io::copy(&mut reader.take(MAX_TRAILING_DATA), &mut io::sink())? as usize;
+            if trailing > 0 {
+                return Err(CartridgeError::new_trailing_data(trailing));
+            }
+        }
+
+        Ok((cart, budget.consumed))
+    }
+
+    /// Reads the rest of a cartridge with the reader of its file version,
+    /// upgrading it to the current Cartridge.
+    ///
+    /// Each file version has its own reader, so older files keep loading as
+    /// the format evolves. `save` always writes the latest version.
+    fn migrate<R: Read>(
+        version: u8,
+        header: &CartridgeHeader,
+        reader: &mut R,
+        options: &ReadOptions,
+        budget: &mut ReadBudget,
+    ) -> Result<Cartridge> {
+        match version {
+            1 => Self::read_v1(header, reader, options, budget),
+            2 => Self::read_v2(header, reader, options, budget),
+            _ => Err(CartridgeError::new_unsupported_version(version)),
+        }
+    }
+
+    /// Reads the data after the header of a version 1 cartridge file.
+    ///
+    /// Version 1 only differs in the font, which has its glyph pixels stored
+    /// column by column, so they are transposed to rows.
+    fn read_v1<R: Read>(
+        header: &CartridgeHeader,
+        reader: &mut R,
+        options: &ReadOptions,
+        budget: &mut ReadBudget,
+    ) -> Result<Cartridge> {
+        let mut cart = Self::read_v2(header, reader, options, budget)?;
+
+        for glyph in cart.font.chunks_exact_mut(GLYPH_BYTES) {
+            for y in 0..GLYPH_WIDTH {
+                for x in (y + 1)..GLYPH_WIDTH {
+                    glyph.swap(y * GLYPH_WIDTH + x, x * GLYPH_WIDTH + y);
+                }
+            }
+        }
+
+        Ok(cart)
+    }
+
+    /// Reads the data after the header of a version 2 cartridge file.
+    fn read_v2<R: Read>(
+        header: &CartridgeHeader,
+        reader: &mut R,
+        options: &ReadOptions,
+        budget: &mut ReadBudget,
+    ) -> Result<Cartridge> {
+        let mut cart = Cartridge::default();
+
+        budget.reserve(
+            1 + header.name_size as usize + header.desc_size as usize + header.author_size as usize,
+        )?;
         cart.version = reader.read_u8()?;
 
         let mut name = vec![0u8; header.name_size as usize];
         reader.read_exact(&mut name)?;
-        cart.name = String::from_utf8(name)?;
+        cart.name = options.decode_string(name)?;
 
         let mut desc = vec![0u8; header.desc_size as usize];
         reader.read_exact(&mut desc)?;
-        cart.desc = String::from_utf8(desc)?;
+        cart.desc = options.decode_string(desc)?;
 
         let mut author = vec![0u8; header.author_size as usize];
         reader.read_exact(&mut author)?;
-        cart.author = String::from_utf8(author)?;
+        cart.author = options.decode_string(author)?;
 
+        let mut ended = false;
         loop {
-            let chunk = Chunk::from_reader(reader)?;
+            budget.reserve(CHUNK_HEADER_SIZE)?;
+            let chunk_header = match ChunkHeader::from_reader_or_end(reader)? {
+                Some(chunk_header) => chunk_header,
+                None if ended => break,
+                None => return Err(CartridgeError::new_missing_end_chunk()),
+            };
+
+            budget.reserve(chunk_header.size())?;
+            let chunk = Chunk::from_header_and_reader(chunk_header, reader)?;
+            ended = chunk.chunk_type() == ChunkType::End;
+
+            if let Some(section) = Section::from_chunk_type(chunk.chunk_type()) {
+                if chunk.data().is_empty() {
+                    cart.empty_sections.insert(section);
+                } else {
+                    cart.empty_sections.remove(&section);
+                }
+            }
 
             match chunk.chunk_type() {
+                ChunkType::End if options.multi => {}
                 ChunkType::End => {
                     break;
                 }
@@ -112,7 +395,7 @@ impl Cartridge {
                     cart.cover.clone_from(chunk.data());
                 }
                 ChunkType::Code => {
-                    cart.code = String::from_utf8(chunk.data().clone())?;
+                    cart.code = options.decode_string(chunk.into_data())?;
                 }
                 ChunkType::Font => {
                     cart.font.clone_from(chunk.data());
@@ -123,44 +406,298 @@ impl Cartridge {
                 ChunkType::Map => {
                     cart.map.clone_from(chunk.data());
                 }
+                ChunkType::Language => {
+                    let name = options.decode_string(chunk.into_data())?;
+                    cart.code_language = if name.is_empty() {
+                        None
+                    } else {
+                        Some(CodeLanguage::from_name(&name))
+                    };
+                }
             }
         }
 
         Ok(cart)
     }
 
+    /// Saves the Cartridge data into a Writer.
+    ///
+    /// Chunks are always written in the same order, cover, code, code
+    /// language, font, palette and map, followed by the End chunk. Sections
+    /// without data are skipped unless they are in `empty_sections`, so
+    /// saving the same Cartridge always writes the same bytes.
     pub fn save<W: Write>(&self, writer: &mut W) -> Result<()> {
-        let header = CartridgeHeader {
-            name_size: self.name.len() as u8,
-            desc_size: self.desc.len() as u16,
-            author_size: self.author.len() as u8,
-            ..Default::default()
+        let mut cart_writer =
+            CartridgeWriter::new(writer, self.version, &self.name, &self.desc, &self.author)?;
+
+        for (chunk_type, data) in self.saved_chunks() {
+            cart_writer.write_section(chunk_type, data)?;
+        }
+
+        cart_writer.finish()?;
+
+        Ok(())
+    }
+
+    /// Returns the Cartridge data as bytes, as written by `save`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(self.saved_size());
+        self.save(&mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    /// Returns the number of bytes written by `save`.
+    pub fn saved_size(&self) -> usize {
+        let metadata = 1 + self.name.len() + self.desc.len() + self.author.len();
+        let chunks: usize = self
+            .saved_chunks()
+            .iter()
+            .map(|(_, data)| CHUNK_HEADER_SIZE + data.len())
+            .sum();
+
+        CARTRIDGE_HEADER_SIZE + metadata + chunks + CHUNK_HEADER_SIZE
+    }
+
+    /// Returns the chunks of the present sections, in the order they are saved.
+    fn saved_chunks(&self) -> Vec<(ChunkType, &[u8])> {
+        Section::ALL
+            .iter()
+            .filter(|section| self.has_section(**section))
+            .map(|section| (ChunkType::from(*section), self.raw_section(*section)))
+            .collect()
+    }
+
+    /// Returns the code language, if specified.
+    pub fn code_language(&self) -> Option<&CodeLanguage> {
+        self.code_language.as_ref()
+    }
+
+    /// Sets the code language, or clears it with `None`.
+    pub fn set_code_language(&mut self, code_language: Option<CodeLanguage>) {
+        self.code_language = code_language;
+    }
+
+    /// Returns the sections without data that are still saved, as zero-size
+    /// chunks.
+    pub fn empty_sections(&self) -> &BTreeSet<Section> {
+        &self.empty_sections
+    }
+
+    /// Returns a mutable reference to the sections without data that are
+    /// still saved, as zero-size chunks.
+    pub fn empty_sections_mut(&mut self) -> &mut BTreeSet<Section> {
+        &mut self.empty_sections
+    }
+
+    /// Returns true if a section is present, because it has data or is in
+    /// `empty_sections`.
+    ///
+    /// Only present sections are saved.
+    pub fn has_section(&self, section: Section) -> bool {
+        !self.raw_section(section).is_empty() || self.empty_sections.contains(&section)
+    }
+
+    /// Returns the raw data of a section, as saved in its chunk.
+    pub fn raw_section(&self, section: Section) -> &[u8] {
+        match section {
+            Section::Cover => &self.cover,
+            Section::Code => self.code.as_bytes(),
+            Section::Language => self
+                .code_language
+                .as_ref()
+                .map(|language| language.name().as_bytes())
+                .filter(|name| !name.is_empty())
+                .unwrap_or_default(),
+            Section::Font => &self.font,
+            Section::Palette => &self.palette,
+            Section::Map => &self.map,
+        }
+    }
+
+    /// Sets the palette data from a Palette.
+    ///
+    /// A Palette always has 16 colors, so the data is always a valid 48 bytes
+    /// palette chunk.
+    pub fn set_palette(&mut self, palette: &Palette) {
+        self.palette = palette.to_bytes();
+    }
+
+    /// Replaces the palette, remapping the map and cover palette indices to
+    /// the nearest colors of the new palette.
+    ///
+    /// Indices out of the current palette are kept. Returns an error if the
+    /// current palette data isn't a valid palette chunk.
+    pub fn remap_palette(&mut self, new_palette: &Palette) -> Result<()> {
+        let old_palette = Palette::from_cartridge_bytes(&self.palette)?;
+        let remap: Vec<u8> = old_palette
+            .iter()
+            .take(self.palette.len() / CARTRIDGE_BYTES_PER_COLOR)
+            .map(|color| new_palette.nearest(*color) as u8)
+            .collect();
+        let remap_index = |index: &mut u8| {
+            if let Some(new_index) = remap.get(*index as usize) {
+                *index = *new_index;
+            }
         };
-        header.save(writer)?;
 
-        writer.write_u8(self.version)?;
-        writer.write_all(self.name.as_bytes())?;
-        writer.write_all(self.desc.as_bytes())?;
-        writer.write_all(self.author.as_bytes())?;
+        self.map.iter_mut().skip(1).step_by(2).for_each(remap_index);
+        self.cover.iter_mut().for_each(remap_index);
+        self.set_palette(new_palette);
 
-        let chunks = vec![
-            (self.cover.clone(), ChunkType::Cover),
-            (self.code.as_bytes().to_vec(), ChunkType::Code),
-            (self.font.clone(), ChunkType::Font),
-            (self.palette.clone(), ChunkType::Palette),
-            (self.map.clone(), ChunkType::Map),
-        ];
+        Ok(())
+    }
+
+    /// Sets the font data from a Font.
+    ///
+    /// A Font always has 256 glyphs, so the data is always a valid font chunk.
+    pub fn set_font(&mut self, font: &Font) {
+        self.font = font.to_bytes();
+    }
+
+    /// Returns the cover as a Screen, resolving its indices to the Palette colors.
+    ///
+    /// An empty cover returns a cleared Screen.
+    pub fn cover_image(&self, palette: &Palette) -> Result<Screen> {
+        let mut screen = Screen::default();
+        if self.cover.is_empty() {
+            return Ok(screen);
+        }
+
+        let area = screen.width() * screen.height();
+        if self.cover.len() != area {
+            return Err(CartridgeError::new_invalid_chunk_size(
+                ChunkType::Cover,
+                self.cover.len(),
+                vec![0, area],
+            ));
+        }
+
+        for (pixel, index) in screen.iter_mut().zip(self.cover.iter()) {
+            *pixel = palette.get_color(*index as usize)?;
+        }
+
+        Ok(screen)
+    }
+
+    /// Renders the map into a Screen, with its font and palette.
+    ///
+    /// Each tile is drawn as its glyph in its color, from the top-left tile.
+    /// The map is larger than the screen, so tiles past the screen edges are
+    /// clipped and never read. A missing font renders nothing and a missing
+    /// palette renders black, while invalid section data or indices of drawn
+    /// tiles return an error.
+    pub fn render_map_to_screen(&self) -> Result<Screen> {
+        let font = if self.font.is_empty() {
+            Font::default()
+        } else {
+            Font::from_bytes(&self.font)?
+        };
+        let palette = Palette::from_cartridge_bytes(&self.palette)?;
+        let tile_size = font.glyph(0)?.size();
+
+        let mut screen = Screen::default();
+        for (index, tile) in self.map.chunks_exact(2).enumerate() {
+            let origin = Coord::new(
+                (index % MAP_WIDTH) * tile_size.width(),
+                (index / MAP_WIDTH) * tile_size.height(),
+            );
+            if origin.y >= screen.height() {
+                break;
+            }
+            if origin.x >= screen.width() {
+                continue;
+            }
+
+            let glyph = font.glyph(tile[0] as usize)?;
+            let color = palette.get_color(tile[1] as usize)?;
+            screen.draw_glyph(origin, glyph, color)?;
+        }
+
+        Ok(screen)
+    }
+
+    /// Returns a summary of the sections that differ from another Cartridge.
+    pub fn diff(&self, other: &Cartridge) -> CartridgeDiff {
+        CartridgeDiff::new(self, other)
+    }
+}
 
-        for (data, chunk_type) in chunks.into_iter().filter(|(d, _)| !d.is_empty()) {
-            let chunk = Chunk::new(chunk_type, data);
-            chunk.save(writer)?;
+/// Checks if a text field fits in its header size.
+fn validate_field_size(field: &'static str, value: &str, max: usize) -> Result<()> {
+    if value.len() > max {
+        return Err(CartridgeError::new_field_too_long(field, value.len(), max));
+    }
+
+    Ok(())
+}
+
+/// A iterator over the chunks of a cartridge read from a Reader.
+///
+/// The iterator stops after the End chunk or the first error.
+pub struct CartridgeChunks<R> {
+    reader: R,
+    started: bool,
+    finished: bool,
+}
+
+impl<R: Read> CartridgeChunks<R> {
+    fn new(reader: R) -> Self {
+        Self {
+            reader,
+            started: false,
+            finished: false,
         }
+    }
 
-        let chunk = Chunk::default();
-        chunk.save(writer)?;
+    fn skip_metadata(&mut self) -> Result<()> {
+        let header = CartridgeHeader::from_reader(&mut self.reader)?;
+
+        let size =
+            1 + header.name_size as usize + header.desc_size as usize + header.author_size as usize;
+        let mut metadata = vec![0u8; size];
+        self.reader.read_exact(&mut metadata)?;
 
         Ok(())
     }
+
+    fn read_chunk(&mut self) -> Result<Option<(ChunkType, Vec<u8>)>> {
+        if !self.started {
+            self.started = true;
+            self.skip_metadata()?;
+        }
+
+        let chunk_header = ChunkHeader::from_reader_or_end(&mut self.reader)?
+            .ok_or_else(CartridgeError::new_missing_end_chunk)?;
+        let chunk = Chunk::from_header_and_reader(chunk_header, &mut self.reader)?;
+
+        match chunk.chunk_type() {
+            ChunkType::End => Ok(None),
+            chunk_type => Ok(Some((chunk_type, chunk.into_data()))),
+        }
+    }
+}
+
+impl<R: Read> Iterator for CartridgeChunks<R> {
+    type Item = Result<(ChunkType, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        match self.read_chunk() {
+            Ok(Some(chunk)) => Some(Ok(chunk)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(error) => {
+                self.finished = true;
+                Some(Err(error))
+            }
+        }
+    }
 }
 
 impl Default for Cartridge {
@@ -175,16 +712,38 @@ impl Default for Cartridge {
             palette: vec![],
             map: vec![],
             code: "".to_string(),
+            code_language: None,
+            empty_sections: BTreeSet::new(),
         }
     }
 }
 
+impl PartialEq for Cartridge {
+    /// Compares the cartridge data, ignoring `empty_sections`, which only
+    /// changes how sections without data are saved.
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version
+            && self.name == other.name
+            && self.desc == other.desc
+            && self.author == other.author
+            && self.cover == other.cover
+            && self.font == other.font
+            && self.palette == other.palette
+            && self.map == other.map
+            && self.code == other.code
+            && self.code_language == other.code_language
+    }
+}
+
 #[cfg(test)]
 mod test_super {
     use std::io::Cursor;
 
     use assert_matches::assert_matches;
 
+    use crate::common::CommonError;
+    use crate::graphic::{Color, GlyphPixel};
+
     use super::*;
 
     #[test]
@@ -252,6 +811,47 @@ mod test_super {
         assert_eq!(header.author_size, DEFAULT_AUTHOR_SIZE);
     }
 
+    #[test]
+    fn test_cartridge_new() {
+        let result = Cartridge::new("Dungeons of the Dungeon", "Luiz de Prá");
+        assert!(result.is_ok());
+
+        let cart = result.unwrap();
+        assert_eq!(cart.name, "Dungeons of the Dungeon");
+        assert_eq!(cart.author, "Luiz de Prá");
+        assert_eq!(cart.version, DEFAULT_VERSION);
+        assert_eq!(cart.desc, "");
+        assert!(cart.cover.is_empty());
+        assert!(cart.font.is_empty());
+        assert!(cart.palette.is_empty());
+        assert!(cart.map.is_empty());
+        assert!(cart.code.is_empty());
+    }
+
+    #[test]
+    fn test_cartridge_new_name_too_long() {
+        let name = "a".repeat(MAX_NAME_SIZE + 1);
+
+        let result = Cartridge::new(name, "me");
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CartridgeError::FieldTooLong(f, v, e) if f == "name" && v == MAX_NAME_SIZE + 1 && e == MAX_NAME_SIZE
+        );
+    }
+
+    #[test]
+    fn test_cartridge_new_author_too_long() {
+        let author = "a".repeat(MAX_AUTHOR_SIZE + 1);
+
+        let result = Cartridge::new("name", author);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CartridgeError::FieldTooLong(f, v, e) if f == "author" && v == MAX_AUTHOR_SIZE + 1 && e == MAX_AUTHOR_SIZE
+        );
+    }
+
     #[test]
     fn test_cartridge_from_reader() {
         let mut data = vec![
@@ -315,6 +915,8 @@ mod test_super {
             palette: vec![0, 0, 0, 255, 255, 255, 180, 180, 180, 90, 90, 90],
             map: vec![],
             code: "main()".to_string(),
+            code_language: None,
+            empty_sections: BTreeSet::new(),
         };
 
         let result = Cartridge::from_reader(&mut reader);
@@ -322,6 +924,67 @@ mod test_super {
         assert_eq!(result.unwrap(), expected);
     }
 
+    #[test]
+    fn test_cartridge_partialeq_ignores_empty_sections() {
+        let cart = Cartridge::default();
+        let mut other = Cartridge::default();
+        other.empty_sections_mut().insert(Section::Font);
+
+        assert_eq!(cart, other);
+        assert!(other.has_section(Section::Font));
+        assert!(!cart.has_section(Section::Font));
+    }
+
+    #[test]
+    fn test_cartridge_has_section() {
+        let mut reader = Cursor::new(vec![
+            // header
+            1, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+            // cart
+            1, // version
+            // empty font chunk
+            3, 0, 0, 0, 0, // code chunk
+            2, 2, 0, 0, 0, 104, 105, // end
+            0, 0, 0, 0, 0,
+        ]);
+
+        let result = Cartridge::from_reader(&mut reader);
+        assert!(result.is_ok());
+        let cart = result.unwrap();
+
+        assert!(cart.has_section(Section::Font));
+        assert!(cart.has_section(Section::Code));
+        assert!(!cart.has_section(Section::Palette));
+        assert!(!cart.has_section(Section::Map));
+
+        let bytes = cart.to_bytes().unwrap();
+        let loaded = Cartridge::from_bytes(&bytes).unwrap();
+        assert!(loaded.has_section(Section::Font));
+        assert!(!loaded.has_section(Section::Palette));
+        assert_eq!(loaded, cart);
+        assert_eq!(bytes.len(), cart.saved_size());
+    }
+
+    #[test]
+    fn test_cartridge_has_section_cleared_by_data() {
+        let mut cart = Cartridge::default();
+        cart.empty_sections_mut().insert(Section::Code);
+        assert!(cart.has_section(Section::Code));
+
+        let mut reader = Cursor::new(vec![
+            1, 0, 0, 0, 0, 1, // header and version
+            2, 0, 0, 0, 0, // empty code chunk
+            2, 1, 0, 0, 0, 104, // code chunk
+            0, 0, 0, 0, 0, // end
+        ]);
+        let loaded = Cartridge::from_reader_multi(&mut reader).unwrap();
+        assert!(loaded.has_section(Section::Code));
+        assert!(loaded.empty_sections().is_empty());
+    }
+
     #[test]
     fn test_cartridge_from_reader_empty_data_and_chunks() {
         let mut reader = Cursor::new(vec![
@@ -345,6 +1008,8 @@ mod test_super {
             palette: vec![],
             map: vec![],
             code: "".to_string(),
+            code_language: None,
+            empty_sections: BTreeSet::new(),
         };
 
         let result = Cartridge::from_reader(&mut reader);
@@ -353,8 +1018,8 @@ mod test_super {
     }
 
     #[test]
-    fn test_cartridge_from_reader_missing_data() {
-        let mut reader = Cursor::new(vec![
+    fn test_cartridge_from_reader_strict() {
+        let mut data = vec![
             // header
             1, // cart version
             0, // name size
@@ -362,45 +1027,462 @@ mod test_super {
             0, // author size
             // cart
             1, // version
-        ]);
+            // end
+            0, 0, 0, 0, 0,
+        ];
 
-        let result = Cartridge::from_reader(&mut reader);
+        let result = Cartridge::from_reader_strict(&mut Cursor::new(data.clone()));
+        assert!(result.is_ok());
+
+        // junk data
+        data.extend_from_slice(&[1, 0, 0]);
+
+        let result = Cartridge::from_reader_strict(&mut Cursor::new(data.clone()));
         assert!(result.is_err());
-        assert_matches!(result.unwrap_err(), CartridgeError::Io(_));
+        assert_matches!(result.unwrap_err(), CartridgeError::TrailingData(3));
+
+        let result = Cartridge::from_reader(&mut Cursor::new(data));
+        assert!(result.is_ok());
     }
 
     #[test]
-    fn test_cartridge_from_reader_missing_end_chunk() {
-        let mut reader = Cursor::new(vec![
+    fn test_cartridge_from_reader_strict_endless_trailing_data() {
+        let data = vec![
             // header
             1, // cart version
             0, // name size
             0, 0, // desc size
             0, // author size
-        ]);
+            // cart
+            1, // version
+            // end chunk
+            0, 0, 0, 0, 0,
+        ];
+        let mut reader = Cursor::new(data).chain(io::repeat(0));
 
-        let result = Cartridge::from_reader(&mut reader);
+        let result = Cartridge::from_reader_strict(&mut reader);
         assert!(result.is_err());
-        assert_matches!(result.unwrap_err(), CartridgeError::Io(_));
+        assert_matches!(
+            result.unwrap_err(),
+            CartridgeError::TrailingData(size) if size == MAX_TRAILING_DATA as usize
+        );
     }
 
     #[test]
-    fn test_cartridge_save() {
-        let cart = Cartridge {
-            version: 11,
-            name: "thisisname".to_string(),
-            desc: "descrição".to_string(),
-            author: "me".to_string(),
-            cover: vec![],
-            font: vec![0; 16384],
-            palette: vec![0, 0, 0, 255, 255, 255, 180, 180, 180, 90, 90, 90],
-            map: vec![],
-            code: "main()".to_string(),
-        };
+    fn test_cartridge_from_reader_multi() {
+        let mut data = vec![
+            // header
+            1, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+            // cart
+            1, // version
+        ];
+        // code chunk
+        data.extend_from_slice(&[2, 3, 0, 0, 0, b'o', b'l', b'd']);
+        // end chunk
+        data.extend_from_slice(&[0, 0, 0, 0, 0]);
+        // code chunk
+        data.extend_from_slice(&[2, 3, 0, 0, 0, b'n', b'e', b'w']);
+        // end chunk
+        data.extend_from_slice(&[0, 0, 0, 0, 0]);
+
+        let result = Cartridge::from_reader_multi(&mut Cursor::new(data.clone()));
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().code, "new");
+
+        let result = Cartridge::from_reader(&mut Cursor::new(data));
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().code, "old");
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_multi_missing_end_chunk() {
+        let data = vec![
+            // header
+            1, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+            // cart
+            1, // version
+            // code chunk
+            2, 1, 0, 0, 0, b'a',
+        ];
+
+        let result = Cartridge::from_reader_multi(&mut Cursor::new(data));
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CartridgeError::MissingEndChunk);
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_multi_patch_missing_end_chunk() {
+        let mut data = vec![
+            // header
+            1, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+            // cart
+            1, // version
+        ];
+        // end chunk
+        data.extend_from_slice(&[0, 0, 0, 0, 0]);
+        // code chunk
+        data.extend_from_slice(&[2, 1, 0, 0, 0, b'n']);
+
+        let result = Cartridge::from_reader_multi(&mut Cursor::new(data));
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CartridgeError::MissingEndChunk);
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_invalid_utf8() {
+        let data = vec![
+            // header
+            1, // cart version
+            2, // name size
+            0, 0, // desc size
+            0, // author size
+            // cart
+            1,    // version
+            b'a', // name
+            0xff, // name
+            // end
+            0, 0, 0, 0, 0,
+        ];
+
+        let result = Cartridge::from_reader(&mut Cursor::new(data.clone()));
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CartridgeError::FromUtf8(_));
+
+        let result = Cartridge::from_reader_lossy(&mut Cursor::new(data));
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().name, "a\u{fffd}");
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_missing_data() {
+        let mut reader = Cursor::new(vec![
+            // header
+            1, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+        ]);
+
+        let result = Cartridge::from_reader(&mut reader);
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CartridgeError::Io(_));
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_missing_end_chunk() {
+        let mut reader = Cursor::new(vec![
+            // header
+            1, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+            // cart
+            1, // version
+            // code chunk
+            2, 2, 0, 0, 0, 104, 105,
+        ]);
+
+        let result = Cartridge::from_reader(&mut reader);
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CartridgeError::MissingEndChunk);
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_without_chunks() {
+        let mut reader = Cursor::new(vec![
+            // header
+            1, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+            // cart
+            1, // version
+        ]);
+
+        let result = Cartridge::from_reader(&mut reader);
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CartridgeError::MissingEndChunk);
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_truncated_chunk() {
+        let mut reader = Cursor::new(vec![
+            // header
+            1, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+            // cart
+            1, // version
+            // code chunk cut in the middle of its data
+            2, 2, 0, 0, 0, 104,
+        ]);
+
+        let result = Cartridge::from_reader(&mut reader);
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CartridgeError::Io(_));
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_v1() {
+        let data = vec![
+            // header
+            1, // cart version
+            2, // name size
+            0, 0, // desc size
+            2, // author size
+            // cart
+            3, // version
+            104, 105, // name
+            109, 101, // author
+            // code chunk
+            2, 2, 0, 0, 0, 104, 105, // end
+            0, 0, 0, 0, 0,
+        ];
+        let expected = Cartridge {
+            version: 3,
+            name: "hi".to_string(),
+            author: "me".to_string(),
+            code: "hi".to_string(),
+            ..Default::default()
+        };
+
+        let result = Cartridge::from_reader(&mut Cursor::new(data.clone()));
+        assert!(result.is_ok());
+
+        let cart = result.unwrap();
+        assert_eq!(cart, expected);
+
+        let mut saved = vec![];
+        cart.save(&mut saved).unwrap();
+        assert_eq!(saved[0], DEFAULT_CART_FILE_VERSION);
+        assert_eq!(saved[1..], data[1..]);
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_v1_transposes_font() {
+        let mut data = vec![
+            // header
+            1, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+            // cart
+            1, // version
+            // font chunk
+            3, 0, 64, 0, 0,
+        ];
+        let mut font = vec![0u8; 16384];
+        font[1] = 1;
+        font[GLYPH_BYTES + 2 * GLYPH_WIDTH + 5] = 2;
+        data.extend_from_slice(&font);
+        data.extend_from_slice(&[0, 0, 0, 0, 0]);
+
+        let cart = Cartridge::from_reader(&mut Cursor::new(data)).unwrap();
+
+        let mut expected = vec![0u8; 16384];
+        expected[GLYPH_WIDTH] = 1;
+        expected[GLYPH_BYTES + 5 * GLYPH_WIDTH + 2] = 2;
+        assert_eq!(cart.font, expected);
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_unsupported_version() {
+        let mut reader = Cursor::new(vec![
+            // header
+            3, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+        ]);
+
+        let result = Cartridge::from_reader(&mut reader);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CartridgeError::UnsupportedVersion(v) if v == 3
+        );
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_limited() {
+        let mut reader = Cursor::new(vec![
+            // header
+            1, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+            // cart
+            1, // version
+            // code chunk
+            2, 2, 0, 0, 0, 104, 105, // end
+            0, 0, 0, 0, 0,
+        ]);
+        let expected = Cartridge {
+            code: "hi".to_string(),
+            ..Default::default()
+        };
+
+        let result = Cartridge::from_reader_limited(&mut reader, 18);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), expected);
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_counted() {
+        let cart = Cartridge {
+            name: "name".to_string(),
+            code: "main()".to_string(),
+            palette: vec![255; 12],
+            ..Default::default()
+        };
+        let bytes = cart.to_bytes().unwrap();
+
+        let mut data = bytes.clone();
+        data.extend_from_slice(&[1, 2, 3]);
+        let mut reader = Cursor::new(data);
+
+        let result = Cartridge::from_reader_counted(&mut reader);
+        assert!(result.is_ok());
+        let (loaded, count) = result.unwrap();
+        assert_eq!(loaded, cart);
+        assert_eq!(count, bytes.len());
+        assert_eq!(reader.position() as usize, count);
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_limited_too_large() {
+        let mut data = vec![
+            // header
+            1, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+            // cart
+            1, // version
+        ];
+
+        // a stream of repeated palette chunks
+        for _ in 0..100 {
+            data.extend_from_slice(&[4, 12, 0, 0, 0]);
+            data.extend_from_slice(&[0; 12]);
+        }
+
+        let mut reader = Cursor::new(data);
+
+        let result = Cartridge::from_reader_limited(&mut reader, 256);
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CartridgeError::TooLarge(m) if m == 256);
+        assert!(reader.position() <= 256);
+    }
+
+    #[test]
+    fn test_cartridge_from_reader_limited_huge_chunk() {
+        let mut reader = Cursor::new(vec![
+            // header
+            1, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+            // cart
+            1, // version
+            // code chunk declaring 4GB of data
+            2, 255, 255, 255, 255,
+        ]);
+
+        let result = Cartridge::from_reader_limited(&mut reader, 1024);
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CartridgeError::TooLarge(m) if m == 1024);
+    }
+
+    #[test]
+    fn test_cartridge_chunks() {
+        let cart = Cartridge {
+            name: "name".to_string(),
+            desc: "desc".to_string(),
+            code: "main()".to_string(),
+            font: vec![0; 16384],
+            palette: vec![255; 12],
+            ..Default::default()
+        };
+        let mut data = vec![];
+        cart.save(&mut data).unwrap();
+
+        let chunks: Vec<(ChunkType, Vec<u8>)> = Cartridge::chunks(Cursor::new(data))
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0], (ChunkType::Code, b"main()".to_vec()));
+        assert_eq!(chunks[1], (ChunkType::Font, vec![0; 16384]));
+        assert_eq!(chunks[2], (ChunkType::Palette, vec![255; 12]));
+    }
+
+    #[test]
+    fn test_cartridge_chunks_partial() {
+        let cart = Cartridge {
+            cover: vec![0; 245760],
+            code: "main()".to_string(),
+            ..Default::default()
+        };
+        let mut data = vec![];
+        cart.save(&mut data).unwrap();
+        let total = data.len() as u64;
+
+        let mut reader = Cursor::new(data);
+        let result = Cartridge::chunks(&mut reader).next();
+        assert_matches!(result, Some(Ok((ChunkType::Cover, ref d))) if d.len() == 245760);
+        assert!(reader.position() < total);
+    }
+
+    #[test]
+    fn test_cartridge_chunks_missing_end_chunk() {
+        let reader = Cursor::new(vec![
+            // header
+            1, // cart version
+            0, // name size
+            0, 0, // desc size
+            0, // author size
+            // cart
+            1, // version
+            // code chunk
+            2, 2, 0, 0, 0, 104, 105,
+        ]);
+
+        let mut chunks = Cartridge::chunks(reader);
+        assert_matches!(chunks.next(), Some(Ok((ChunkType::Code, _))));
+        assert_matches!(chunks.next(), Some(Err(CartridgeError::MissingEndChunk)));
+        assert!(chunks.next().is_none());
+    }
+
+    #[test]
+    fn test_cartridge_save() {
+        let cart = Cartridge {
+            version: 11,
+            name: "thisisname".to_string(),
+            desc: "descrição".to_string(),
+            author: "me".to_string(),
+            cover: vec![],
+            font: vec![0; 16384],
+            palette: vec![0, 0, 0, 255, 255, 255, 180, 180, 180, 90, 90, 90],
+            map: vec![],
+            code: "main()".to_string(),
+            code_language: None,
+            empty_sections: BTreeSet::new(),
+        };
 
         let mut expected = vec![
             // cart header
-            1,  // cart version
+            2,  // cart version
             10, // name size
             11, 0, // desc size
             2, // author size
@@ -446,7 +1528,7 @@ mod test_super {
     #[test]
     fn test_cartridge_save_empty() {
         let cart = Cartridge::default();
-        let expected: Vec<u8> = vec![1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0];
+        let expected: Vec<u8> = vec![2, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0];
 
         let mut writer = Cursor::new(vec![0u8; 5]);
         let result = cart.save(&mut writer);
@@ -454,6 +1536,171 @@ mod test_super {
         assert_eq!(writer.get_ref(), &expected);
     }
 
+    #[test]
+    fn test_cartridge_to_bytes_and_from_bytes() {
+        let cart = Cartridge {
+            version: 17,
+            name: "Dungeons of the Dungeon".to_string(),
+            desc: "A cool game about dungeons inside dungeons.".to_string(),
+            author: "Luiz de Prá".to_string(),
+            palette: vec![
+                0x2d, 0x1b, 0x00, 0x1e, 0x60, 0x6e, 0x5a, 0xb9, 0xa8, 0xc4, 0xf0, 0xc2,
+            ],
+            code: "def main:\n    pass".to_string(),
+            ..Default::default()
+        };
+
+        let result = cart.to_bytes();
+        assert!(result.is_ok());
+        let bytes = result.unwrap();
+        assert_eq!(bytes.len(), cart.saved_size());
+
+        let result = Cartridge::from_bytes(&bytes);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), cart);
+    }
+
+    #[test]
+    fn test_cartridge_raw_section() {
+        let cart = Cartridge {
+            palette: vec![
+                0x2d, 0x1b, 0x00, 0x1e, 0x60, 0x6e, 0x5a, 0xb9, 0xa8, 0xc4, 0xf0, 0xc2,
+            ],
+            code: "def main:\n    pass".to_string(),
+            code_language: Some(CodeLanguage::Python),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            cart.raw_section(Section::Palette),
+            &[0x2d, 0x1b, 0x00, 0x1e, 0x60, 0x6e, 0x5a, 0xb9, 0xa8, 0xc4, 0xf0, 0xc2]
+        );
+        assert_eq!(cart.raw_section(Section::Code), b"def main:\n    pass");
+        assert_eq!(cart.raw_section(Section::Language), b"python");
+        assert!(cart.raw_section(Section::Cover).is_empty());
+        assert!(cart.raw_section(Section::Map).is_empty());
+    }
+
+    #[test]
+    fn test_cartridge_from_bytes_missing_data() {
+        let result = Cartridge::from_bytes(&[1, 0, 0, 0, 0]);
+
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CartridgeError::Io(_));
+    }
+
+    #[test]
+    fn test_cartridge_save_is_reproducible() {
+        let cart = Cartridge {
+            name: "name".to_string(),
+            palette: vec![0; 12],
+            code: "main()".to_string(),
+            code_language: Some(CodeLanguage::Lua),
+            ..Default::default()
+        };
+
+        let mut first = Cursor::new(vec![]);
+        assert!(cart.save(&mut first).is_ok());
+        let mut second = Cursor::new(vec![]);
+        assert!(cart.save(&mut second).is_ok());
+
+        assert_eq!(first.get_ref(), second.get_ref());
+        assert_eq!(first.get_ref().len(), cart.saved_size());
+    }
+
+    #[test]
+    fn test_cartridge_saved_size() {
+        let cart = Cartridge::default();
+        assert_eq!(cart.saved_size(), 11);
+
+        let cart = Cartridge {
+            author: "me".to_string(),
+            font: vec![0; 16384],
+            map: vec![],
+            ..Default::default()
+        };
+        let mut writer = Cursor::new(vec![]);
+        assert!(cart.save(&mut writer).is_ok());
+        assert_eq!(cart.saved_size(), writer.get_ref().len());
+        assert_eq!(cart.saved_size(), 5 + 3 + 5 + 16384 + 5);
+    }
+
+    #[test]
+    fn test_cartridge_save_and_load_code_language() {
+        for code_language in [
+            CodeLanguage::Lua,
+            CodeLanguage::Python,
+            CodeLanguage::Custom("fennel".to_string()),
+        ] {
+            let cart = Cartridge {
+                code: "main()".to_string(),
+                code_language: Some(code_language),
+                ..Default::default()
+            };
+
+            let mut writer = Cursor::new(vec![]);
+            assert!(cart.save(&mut writer).is_ok());
+
+            let mut reader = Cursor::new(writer.into_inner());
+            let result = Cartridge::from_reader(&mut reader);
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), cart);
+        }
+    }
+
+    #[test]
+    fn test_cartridge_set_code_language() {
+        let mut cart = Cartridge::default();
+        assert_eq!(cart.code_language(), None);
+
+        cart.set_code_language(Some(CodeLanguage::Lua));
+        assert_eq!(cart.code_language(), Some(&CodeLanguage::Lua));
+        assert!(cart.has_section(Section::Language));
+
+        cart.set_code_language(None);
+        assert_eq!(cart.code_language(), None);
+        assert!(!cart.has_section(Section::Language));
+    }
+
+    #[test]
+    fn test_cartridge_save_and_load_without_code_language() {
+        let cart = Cartridge {
+            code: "main()".to_string(),
+            ..Default::default()
+        };
+
+        let mut writer = Cursor::new(vec![]);
+        assert!(cart.save(&mut writer).is_ok());
+
+        let mut reader = Cursor::new(writer.into_inner());
+        let result = Cartridge::from_reader(&mut reader);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().code_language(), None);
+    }
+
+    #[test]
+    fn test_cartridge_save_and_load_empty_code_language() {
+        let cart = Cartridge {
+            code: "main()".to_string(),
+            code_language: Some(CodeLanguage::Custom(String::new())),
+            ..Default::default()
+        };
+        assert!(!cart.has_section(Section::Language));
+
+        let result = Cartridge::from_bytes(&cart.to_bytes().unwrap());
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().code_language(), None);
+
+        let mut cart = cart;
+        cart.empty_sections_mut().insert(Section::Language);
+
+        let result = Cartridge::from_bytes(&cart.to_bytes().unwrap());
+        assert!(result.is_ok());
+        let loaded = result.unwrap();
+        assert_eq!(loaded.code_language(), None);
+        assert!(loaded.has_section(Section::Language));
+    }
+
     #[test]
     fn test_cartridge_save_error() {
         let cart = Cartridge::default();
@@ -465,6 +1712,311 @@ mod test_super {
         assert_matches!(result.unwrap_err(), CartridgeError::Io(_));
     }
 
+    #[test]
+    fn test_cartridge_save_desc_too_long() {
+        let cart = Cartridge {
+            desc: "a".repeat(70000),
+            ..Default::default()
+        };
+
+        let mut writer = Cursor::new(vec![]);
+        let result = cart.save(&mut writer);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CartridgeError::FieldTooLong(f, v, e) if f == "desc" && v == 70000 && e == MAX_DESC_SIZE
+        );
+        assert!(writer.get_ref().is_empty());
+    }
+
+    #[test]
+    fn test_cartridge_save_name_too_long() {
+        let cart = Cartridge {
+            name: "a".repeat(MAX_NAME_SIZE + 1),
+            ..Default::default()
+        };
+
+        let mut writer = Cursor::new(vec![]);
+        let result = cart.save(&mut writer);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CartridgeError::FieldTooLong(f, v, e) if f == "name" && v == MAX_NAME_SIZE + 1 && e == MAX_NAME_SIZE
+        );
+    }
+
+    #[test]
+    fn test_cartridge_set_palette() {
+        let mut palette = Palette::default();
+        palette.set_color(15, Color::new(255, 128, 0)).unwrap();
+
+        let mut cart = Cartridge::default();
+        cart.set_palette(&palette);
+
+        assert_eq!(cart.palette.len(), 48);
+        assert_eq!(&cart.palette[45..], &[255, 128, 0]);
+    }
+
+    #[test]
+    fn test_cartridge_remap_palette() {
+        let red = Color::new(255, 0, 0);
+        let dark_red = Color::new(200, 10, 10);
+        let blue = Color::new(0, 0, 255);
+
+        let mut old_palette = Palette::default();
+        old_palette.set_color(1, red).unwrap();
+        old_palette.set_color(2, blue).unwrap();
+
+        let mut new_palette = Palette::default();
+        new_palette.set_color(3, blue).unwrap();
+        new_palette.set_color(5, dark_red).unwrap();
+
+        let mut cart = Cartridge {
+            map: vec![7, 1, 8, 2, 9, 1, 10, 20],
+            cover: vec![2, 1, 0],
+            ..Default::default()
+        };
+        cart.set_palette(&old_palette);
+
+        let result = cart.remap_palette(&new_palette);
+        assert!(result.is_ok());
+        assert_eq!(cart.map, vec![7, 5, 8, 3, 9, 5, 10, 20]);
+        assert_eq!(cart.cover, vec![3, 5, 0]);
+        assert_eq!(cart.palette, new_palette.to_bytes());
+    }
+
+    #[test]
+    fn test_cartridge_remap_palette_short_palette() {
+        let red = Color::new(255, 0, 0);
+
+        let mut old_palette = Palette::default();
+        old_palette.set_color(1, red).unwrap();
+
+        let mut new_palette = Palette::default();
+        new_palette.set_color(1, red).unwrap();
+
+        let mut cart = Cartridge {
+            map: vec![0, 0, 0, 1, 0, 7],
+            cover: vec![7, 1],
+            ..Default::default()
+        };
+        cart.palette = old_palette.to_bytes()[..12].to_vec();
+
+        let result = cart.remap_palette(&new_palette);
+        assert!(result.is_ok());
+        assert_eq!(cart.map, vec![0, 0, 0, 1, 0, 7]);
+        assert_eq!(cart.cover, vec![7, 1]);
+
+        let mut cart = Cartridge {
+            map: vec![0, 7],
+            cover: vec![7],
+            ..Default::default()
+        };
+
+        let result = cart.remap_palette(&new_palette);
+        assert!(result.is_ok());
+        assert_eq!(cart.map, vec![0, 7]);
+        assert_eq!(cart.cover, vec![7]);
+    }
+
+    #[test]
+    fn test_cartridge_remap_palette_invalid_palette() {
+        let mut cart = Cartridge {
+            palette: vec![0; 5],
+            map: vec![0, 1],
+            ..Default::default()
+        };
+
+        let result = cart.remap_palette(&Palette::standard());
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CartridgeError::Common(CommonError::InvalidLenght { lenght: l, expected: e }) if l == 5 && e == 48
+        );
+        assert_eq!(cart.map, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_cartridge_render_map_to_screen() {
+        let mut font = Font::default();
+        font.glyphs[3]
+            .set_pixel(Coord::new(1, 2), GlyphPixel::Solid)
+            .unwrap();
+        let palette = Palette::standard();
+
+        let mut map = vec![0u8; 2 * (MAP_WIDTH + 2)];
+        map[2 * (MAP_WIDTH + 1)] = 3;
+        map[2 * (MAP_WIDTH + 1) + 1] = 12;
+
+        let mut cart = Cartridge {
+            map,
+            ..Default::default()
+        };
+        cart.set_font(&font);
+        cart.set_palette(&palette);
+
+        let result = cart.render_map_to_screen();
+        assert!(result.is_ok());
+        let screen = result.unwrap();
+        assert_eq!(
+            screen.get_pixel(Coord::new(9, 10)).unwrap(),
+            palette.colors[12]
+        );
+        assert_eq!(
+            screen
+                .iter()
+                .filter(|pixel| **pixel != Color::default())
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_cartridge_render_map_to_screen_missing_sections() {
+        let cart = Cartridge {
+            map: vec![3, 12],
+            ..Default::default()
+        };
+
+        let result = cart.render_map_to_screen();
+        assert!(result.is_ok());
+        assert!(result
+            .unwrap()
+            .iter()
+            .all(|pixel| *pixel == Color::default()));
+    }
+
+    #[test]
+    fn test_cartridge_render_map_to_screen_invalid_color() {
+        let mut cart = Cartridge {
+            map: vec![0, 16],
+            ..Default::default()
+        };
+        cart.set_palette(&Palette::standard());
+
+        let result = cart.render_map_to_screen();
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CartridgeError::Common(CommonError::InvalidIndex { index: i, lenght: l }) if i == 16 && l == 16
+        );
+    }
+
+    #[test]
+    fn test_cartridge_render_map_to_screen_skips_hidden_tiles() {
+        let mut map = vec![0u8; 2 * MAP_WIDTH];
+        map[2 * (MAP_WIDTH - 1) + 1] = 16;
+
+        let mut cart = Cartridge {
+            map,
+            ..Default::default()
+        };
+        cart.set_palette(&Palette::standard());
+
+        let result = cart.render_map_to_screen();
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_cartridge_cover_image() {
+        let palette = Palette::standard();
+        let mut cover = vec![0u8; 640 * 384];
+        cover[1] = 4;
+        cover[640 * 2 + 3] = 15;
+        let cart = Cartridge {
+            cover,
+            ..Default::default()
+        };
+
+        let result = cart.cover_image(&palette);
+        assert!(result.is_ok());
+        let screen = result.unwrap();
+        assert_eq!(
+            screen.get_pixel(Coord::new(0, 0)).unwrap(),
+            palette.colors[0]
+        );
+        assert_eq!(
+            screen.get_pixel(Coord::new(1, 0)).unwrap(),
+            palette.colors[4]
+        );
+        assert_eq!(
+            screen.get_pixel(Coord::new(3, 2)).unwrap(),
+            palette.colors[15]
+        );
+    }
+
+    #[test]
+    fn test_cartridge_cover_image_empty() {
+        let cart = Cartridge::default();
+
+        let result = cart.cover_image(&Palette::standard());
+        assert!(result.is_ok());
+        assert!(result
+            .unwrap()
+            .iter()
+            .all(|pixel| *pixel == Color::default()));
+    }
+
+    #[test]
+    fn test_cartridge_cover_image_invalid_index() {
+        let mut cover = vec![0u8; 640 * 384];
+        cover[10] = 16;
+        let cart = Cartridge {
+            cover,
+            ..Default::default()
+        };
+
+        let result = cart.cover_image(&Palette::standard());
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CartridgeError::Common(CommonError::InvalidIndex { index: 16, .. })
+        );
+    }
+
+    #[test]
+    fn test_cartridge_set_font() {
+        let mut font = Font::default();
+        font.glyphs[1]
+            .set_pixel(Coord::new(0, 0), GlyphPixel::Solid)
+            .unwrap();
+
+        let mut cart = Cartridge::default();
+        cart.set_font(&font);
+        cart.set_palette(&Palette::default());
+
+        let mut data = vec![];
+        assert!(cart.save(&mut data).is_ok());
+
+        let loaded = Cartridge::from_reader(&mut Cursor::new(data)).unwrap();
+        assert_eq!(loaded.font.len(), 16384);
+        assert_eq!(
+            Font::from_bytes(&loaded.font)
+                .unwrap()
+                .changed_glyphs(&Font::default())
+                .unwrap(),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn test_cartridge_diff() {
+        let cart = Cartridge::default();
+        let other = Cartridge {
+            code: "main()".to_string(),
+            ..Default::default()
+        };
+
+        let diff = cart.diff(&other);
+        assert!(diff.code_changed);
+        assert!(!diff.name_changed);
+        assert!(!diff.desc_changed);
+        assert!(!diff.font_changed);
+        assert!(!diff.palette_changed);
+        assert!(!diff.map_changed);
+        assert!(!diff.cover_changed);
+    }
+
     #[test]
     fn test_cartridge_default() {
         let cart = Cartridge::default();
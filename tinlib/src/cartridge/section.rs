@@ -0,0 +1,84 @@
+//! Section implementation and manipulation.
+use crate::cartridge::chunk::ChunkType;
+
+/// A Cartridge data section, saved as a chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Section {
+    /// Cover data.
+    Cover,
+    /// Code data.
+    Code,
+    /// Code language data.
+    Language,
+    /// Font data.
+    Font,
+    /// Palette data.
+    Palette,
+    /// Map data.
+    Map,
+}
+
+impl Section {
+    /// All sections, in the order they are saved.
+    pub const ALL: [Section; 6] = [
+        Section::Cover,
+        Section::Code,
+        Section::Language,
+        Section::Font,
+        Section::Palette,
+        Section::Map,
+    ];
+
+    /// Returns the section saved in a chunk type, or `None` for the End chunk.
+    pub(crate) fn from_chunk_type(chunk_type: ChunkType) -> Option<Section> {
+        Section::ALL
+            .iter()
+            .copied()
+            .find(|section| ChunkType::from(*section) == chunk_type)
+    }
+}
+
+impl From<Section> for ChunkType {
+    fn from(section: Section) -> Self {
+        match section {
+            Section::Cover => ChunkType::Cover,
+            Section::Code => ChunkType::Code,
+            Section::Language => ChunkType::Language,
+            Section::Font => ChunkType::Font,
+            Section::Palette => ChunkType::Palette,
+            Section::Map => ChunkType::Map,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_super {
+    use super::*;
+
+    #[test]
+    fn test_chunktype_from_section() {
+        let data = [
+            (Section::Cover, ChunkType::Cover),
+            (Section::Code, ChunkType::Code),
+            (Section::Language, ChunkType::Language),
+            (Section::Font, ChunkType::Font),
+            (Section::Palette, ChunkType::Palette),
+            (Section::Map, ChunkType::Map),
+        ];
+
+        for (section, expected) in data.iter() {
+            assert_eq!(ChunkType::from(*section), *expected);
+        }
+    }
+
+    #[test]
+    fn test_section_from_chunk_type() {
+        for section in Section::ALL.iter() {
+            assert_eq!(
+                Section::from_chunk_type(ChunkType::from(*section)),
+                Some(*section)
+            );
+        }
+        assert_eq!(Section::from_chunk_type(ChunkType::End), None);
+    }
+}
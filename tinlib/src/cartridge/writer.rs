@@ -0,0 +1,141 @@
+//! CartridgeWriter implementation and manipulation.
+use std::io::Write;
+
+use byteorder::WriteBytesExt;
+
+use crate::cartridge::chunk::{Chunk, ChunkHeader, ChunkType};
+use crate::cartridge::error::{CartridgeError, Result};
+use crate::cartridge::{
+    validate_field_size, CartridgeHeader, MAX_AUTHOR_SIZE, MAX_DESC_SIZE, MAX_NAME_SIZE,
+};
+
+/// A writer streaming a cartridge section by section.
+///
+/// The header and metadata are written on creation, each section when it's
+/// given, and the End chunk on `finish`, so the whole cartridge is never held
+/// in memory.
+#[derive(Debug)]
+pub struct CartridgeWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> CartridgeWriter<W> {
+    /// Creates a CartridgeWriter, writing the header and metadata.
+    ///
+    /// Returns an error if a text field doesn't fit in its header size.
+    pub fn new(mut writer: W, version: u8, name: &str, desc: &str, author: &str) -> Result<Self> {
+        validate_field_size("name", name, MAX_NAME_SIZE)?;
+        validate_field_size("desc", desc, MAX_DESC_SIZE)?;
+        validate_field_size("author", author, MAX_AUTHOR_SIZE)?;
+
+        let header = CartridgeHeader {
+            name_size: name.len() as u8,
+            desc_size: desc.len() as u16,
+            author_size: author.len() as u8,
+            ..Default::default()
+        };
+        header.save(&mut writer)?;
+
+        writer.write_u8(version)?;
+        writer.write_all(name.as_bytes())?;
+        writer.write_all(desc.as_bytes())?;
+        writer.write_all(author.as_bytes())?;
+
+        Ok(Self { writer })
+    }
+
+    /// Writes a section chunk, validating its size for the chunk type first.
+    ///
+    /// Nothing is written for invalid sizes. An End chunk ends the cartridge
+    /// for readers, so it returns an `InvalidChunkType` error and `finish`
+    /// writes it instead.
+    pub fn write_section(&mut self, chunk_type: ChunkType, data: &[u8]) -> Result<()> {
+        if chunk_type == ChunkType::End {
+            return Err(CartridgeError::new_invalid_chunk_type(chunk_type as u8));
+        }
+        Chunk::validate_size(chunk_type, data.len())?;
+
+        ChunkHeader::new(chunk_type, data.len())?.save(&mut self.writer)?;
+        self.writer.write_all(data)?;
+
+        Ok(())
+    }
+
+    /// Writes the End chunk, returning the inner writer.
+    pub fn finish(mut self) -> Result<W> {
+        Chunk::default().save(&mut self.writer)?;
+        self.writer.flush()?;
+
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use crate::cartridge::Cartridge;
+
+    use super::*;
+
+    #[test]
+    fn test_cartridgewriter() {
+        let palette = vec![255; 12];
+
+        let mut writer = CartridgeWriter::new(Vec::new(), 3, "name", "desc", "me").unwrap();
+        assert!(writer.write_section(ChunkType::Palette, &palette).is_ok());
+        assert!(writer.write_section(ChunkType::Code, b"main()").is_ok());
+        let bytes = writer.finish().unwrap();
+
+        let expected = Cartridge {
+            version: 3,
+            name: "name".to_string(),
+            desc: "desc".to_string(),
+            author: "me".to_string(),
+            palette,
+            code: "main()".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(Cartridge::from_bytes(&bytes).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_cartridgewriter_invalid_section_size() {
+        let mut writer = CartridgeWriter::new(Vec::new(), 1, "", "", "").unwrap();
+
+        let result = writer.write_section(ChunkType::Palette, &[0; 5]);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CartridgeError::InvalidChunkSize(t, s, _) if t == ChunkType::Palette && s == 5
+        );
+
+        let bytes = writer.finish().unwrap();
+        assert_eq!(Cartridge::from_bytes(&bytes).unwrap(), Cartridge::default());
+    }
+
+    #[test]
+    fn test_cartridgewriter_end_section() {
+        let mut writer = CartridgeWriter::new(Vec::new(), 1, "", "", "").unwrap();
+
+        let result = writer.write_section(ChunkType::End, &[]);
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CartridgeError::InvalidChunkType(0));
+
+        assert!(writer.write_section(ChunkType::Code, b"main()").is_ok());
+        let bytes = writer.finish().unwrap();
+        assert_eq!(Cartridge::from_bytes(&bytes).unwrap().code, "main()");
+    }
+
+    #[test]
+    fn test_cartridgewriter_field_too_long() {
+        let name = "a".repeat(MAX_NAME_SIZE + 1);
+
+        let result = CartridgeWriter::new(Vec::new(), 1, &name, "", "");
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CartridgeError::FieldTooLong(f, v, e) if f == "name" && v == MAX_NAME_SIZE + 1 && e == MAX_NAME_SIZE
+        );
+    }
+}
@@ -1,9 +1,12 @@
 //! Coord implementation and manipulation.
-use std::slice;
+use core::slice;
 
 use crate::common::size::Size;
 
 /// A Coord representation.
+///
+/// The derived ordering compares `x` before `y`. Use `reading_order_key` to sort
+/// Coords in row-major reading order.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, PartialOrd, Ord)]
 pub struct Coord {
     pub x: usize,
@@ -15,6 +18,41 @@ impl Coord {
     pub fn new(x: usize, y: usize) -> Self {
         Self { x, y }
     }
+
+    /// Returns a new Coord moved by signed offsets, or `None` if it would be negative.
+    pub fn offset(self, dx: isize, dy: isize) -> Option<Self> {
+        let x = add_signed(self.x, dx)?;
+        let y = add_signed(self.y, dy)?;
+
+        Some(Self::new(x, y))
+    }
+
+    /// Returns a key that sorts Coords top-to-bottom, left-to-right.
+    pub fn reading_order_key(self) -> (usize, usize) {
+        (self.y, self.x)
+    }
+
+    /// Returns an iterator over the Bresenham line cells to another Coord, inclusive.
+    pub fn line_to(self, other: Coord) -> LineIter {
+        LineIter::new(self, other)
+    }
+}
+
+/// Creates a Coord from signed components, or `None` if any is negative.
+pub fn coord_from_signed(x: isize, y: isize) -> Option<Coord> {
+    if x < 0 || y < 0 {
+        return None;
+    }
+
+    Some(Coord::new(x as usize, y as usize))
+}
+
+fn add_signed(value: usize, delta: isize) -> Option<usize> {
+    if delta < 0 {
+        value.checked_sub(delta.unsigned_abs())
+    } else {
+        value.checked_add(delta as usize)
+    }
 }
 
 impl From<(usize, usize)> for Coord {
@@ -29,7 +67,7 @@ impl From<[usize; 2]> for Coord {
     }
 }
 
-/// A iterator over all Coord limited by Size.
+/// A iterator over all Coord limited by Size, in row-major order.
 pub struct CoordIter {
     size: Size,
     coord: Coord,
@@ -49,16 +87,76 @@ impl Iterator for CoordIter {
     type Item = Coord;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.coord.x == self.size.height() {
+        if self.coord.y == self.size.height() || self.size.width() == 0 {
             return None;
         }
 
         let result = self.coord;
 
-        self.coord.y += 1;
-        if self.coord.y == self.size.width() {
-            self.coord.y = 0;
-            self.coord.x += 1;
+        self.coord.x += 1;
+        if self.coord.x == self.size.width() {
+            self.coord.x = 0;
+            self.coord.y += 1;
+        }
+
+        Some(result)
+    }
+}
+
+/// A iterator over the Coords of a line between two Coords, inclusive.
+pub struct LineIter {
+    current: (isize, isize),
+    end: (isize, isize),
+    delta: (isize, isize),
+    step: (isize, isize),
+    error: isize,
+    done: bool,
+}
+
+impl LineIter {
+    /// Creates a new LineIter between two Coords.
+    pub fn new(start: Coord, end: Coord) -> Self {
+        let current = (start.x as isize, start.y as isize);
+        let end = (end.x as isize, end.y as isize);
+        let delta = ((end.0 - current.0).abs(), -(end.1 - current.1).abs());
+        let step = (
+            if current.0 < end.0 { 1 } else { -1 },
+            if current.1 < end.1 { 1 } else { -1 },
+        );
+
+        Self {
+            current,
+            end,
+            delta,
+            step,
+            error: delta.0 + delta.1,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for LineIter {
+    type Item = Coord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = Coord::new(self.current.0 as usize, self.current.1 as usize);
+
+        if self.current == self.end {
+            self.done = true;
+        } else {
+            let double_error = 2 * self.error;
+            if double_error >= self.delta.1 {
+                self.error += self.delta.1;
+                self.current.0 += self.step.0;
+            }
+            if double_error <= self.delta.0 {
+                self.error += self.delta.0;
+                self.current.1 += self.step.1;
+            }
         }
 
         Some(result)
@@ -122,6 +220,123 @@ mod test {
         assert_eq!(coord.y, 27);
     }
 
+    #[test]
+    fn test_coord_offset() {
+        let coord = Coord::new(5, 5);
+
+        assert_eq!(coord.offset(-2, 3), Some(Coord::new(3, 8)));
+        assert_eq!(coord.offset(0, 0), Some(coord));
+        assert_eq!(coord.offset(-5, -5), Some(Coord::new(0, 0)));
+    }
+
+    #[test]
+    fn test_coord_offset_negative() {
+        let coord = Coord::new(0, 0);
+
+        assert_eq!(coord.offset(-1, 0), None);
+        assert_eq!(coord.offset(0, -1), None);
+    }
+
+    #[test]
+    fn test_coord_offset_overflow() {
+        let coord = Coord::new(usize::MAX, 0);
+
+        assert_eq!(coord.offset(1, 0), None);
+    }
+
+    #[test]
+    fn test_coord_from_signed() {
+        assert_eq!(coord_from_signed(0, 0), Some(Coord::new(0, 0)));
+        assert_eq!(coord_from_signed(3, 7), Some(Coord::new(3, 7)));
+        assert_eq!(
+            coord_from_signed(isize::MAX, 1),
+            Some(Coord::new(isize::MAX as usize, 1))
+        );
+    }
+
+    #[test]
+    fn test_coord_from_signed_negative() {
+        assert_eq!(coord_from_signed(-1, 0), None);
+        assert_eq!(coord_from_signed(0, -1), None);
+        assert_eq!(coord_from_signed(isize::MIN, isize::MIN), None);
+    }
+
+    #[test]
+    fn test_coord_reading_order_key() {
+        let mut coords = vec![
+            Coord::new(1, 1),
+            Coord::new(0, 2),
+            Coord::new(2, 0),
+            Coord::new(0, 1),
+            Coord::new(0, 0),
+        ];
+        coords.sort_by_key(|coord| coord.reading_order_key());
+
+        assert_eq!(
+            coords,
+            vec![
+                Coord::new(0, 0),
+                Coord::new(2, 0),
+                Coord::new(0, 1),
+                Coord::new(1, 1),
+                Coord::new(0, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coord_line_to_horizontal() {
+        let coords: Vec<Coord> = Coord::new(1, 2).line_to(Coord::new(4, 2)).collect();
+
+        assert_eq!(
+            coords,
+            vec![
+                Coord::new(1, 2),
+                Coord::new(2, 2),
+                Coord::new(3, 2),
+                Coord::new(4, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coord_line_to_diagonal() {
+        let coords: Vec<Coord> = Coord::new(3, 3).line_to(Coord::new(0, 0)).collect();
+
+        assert_eq!(
+            coords,
+            vec![
+                Coord::new(3, 3),
+                Coord::new(2, 2),
+                Coord::new(1, 1),
+                Coord::new(0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coord_line_to_steep() {
+        let coords: Vec<Coord> = Coord::new(0, 0).line_to(Coord::new(1, 3)).collect();
+
+        assert_eq!(
+            coords,
+            vec![
+                Coord::new(0, 0),
+                Coord::new(0, 1),
+                Coord::new(1, 2),
+                Coord::new(1, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coord_line_to_same() {
+        let coord = Coord::new(5, 5);
+        let coords: Vec<Coord> = coord.line_to(coord).collect();
+
+        assert_eq!(coords, vec![coord]);
+    }
+
     #[test]
     fn test_coord_from_tuple() {
         let tuple = (11usize, 27usize);
@@ -154,11 +369,11 @@ mod test {
         let mut iter = CoordIter::new(size);
 
         assert_eq!(iter.next(), Some(Coord::new(0, 0)));
-        assert_eq!(iter.next(), Some(Coord::new(0, 1)));
-        assert_eq!(iter.next(), Some(Coord::new(0, 2)));
         assert_eq!(iter.next(), Some(Coord::new(1, 0)));
+        assert_eq!(iter.next(), Some(Coord::new(2, 0)));
+        assert_eq!(iter.next(), Some(Coord::new(0, 1)));
         assert_eq!(iter.next(), Some(Coord::new(1, 1)));
-        assert_eq!(iter.next(), Some(Coord::new(1, 2)));
+        assert_eq!(iter.next(), Some(Coord::new(2, 1)));
         assert_eq!(iter.next(), None);
     }
 
@@ -171,11 +386,11 @@ mod test {
         let mut enumerate = CoordEnumerate::new(coorditer, itemiter);
 
         assert_eq!(enumerate.next(), Some((Coord::new(0, 0), &1)));
-        assert_eq!(enumerate.next(), Some((Coord::new(0, 1), &2)));
-        assert_eq!(enumerate.next(), Some((Coord::new(0, 2), &3)));
-        assert_eq!(enumerate.next(), Some((Coord::new(1, 0), &4)));
+        assert_eq!(enumerate.next(), Some((Coord::new(1, 0), &2)));
+        assert_eq!(enumerate.next(), Some((Coord::new(2, 0), &3)));
+        assert_eq!(enumerate.next(), Some((Coord::new(0, 1), &4)));
         assert_eq!(enumerate.next(), Some((Coord::new(1, 1), &5)));
-        assert_eq!(enumerate.next(), Some((Coord::new(1, 2), &6)));
+        assert_eq!(enumerate.next(), Some((Coord::new(2, 1), &6)));
         assert_eq!(enumerate.next(), None);
     }
 
@@ -188,11 +403,11 @@ mod test {
         let mut enumerate = CoordEnumerateMut::new(coorditer, itemiter);
 
         assert_eq!(enumerate.next(), Some((Coord::new(0, 0), &mut 1)));
-        assert_eq!(enumerate.next(), Some((Coord::new(0, 1), &mut 2)));
-        assert_eq!(enumerate.next(), Some((Coord::new(0, 2), &mut 3)));
-        assert_eq!(enumerate.next(), Some((Coord::new(1, 0), &mut 4)));
+        assert_eq!(enumerate.next(), Some((Coord::new(1, 0), &mut 2)));
+        assert_eq!(enumerate.next(), Some((Coord::new(2, 0), &mut 3)));
+        assert_eq!(enumerate.next(), Some((Coord::new(0, 1), &mut 4)));
         assert_eq!(enumerate.next(), Some((Coord::new(1, 1), &mut 5)));
-        assert_eq!(enumerate.next(), Some((Coord::new(1, 2), &mut 6)));
+        assert_eq!(enumerate.next(), Some((Coord::new(2, 1), &mut 6)));
         assert_eq!(enumerate.next(), None);
     }
 }
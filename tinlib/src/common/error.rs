@@ -1,5 +1,6 @@
 //! CommonError implementation and manipulation.
-use std::result::Result as StdResult;
+use alloc::string::String;
+use core::result::Result as StdResult;
 
 use thiserror::Error;
 
@@ -15,6 +16,21 @@ pub enum CommonError {
     /// Error to reprense invalid indexes.
     #[error("invalid index {index} for lenght {lenght}")]
     InvalidIndex { index: usize, lenght: usize },
+    /// Error to represent invalid data lenghts.
+    #[error("invalid lenght {lenght}, expected {expected}")]
+    InvalidLenght { lenght: usize, expected: usize },
+    /// Error to represent mismatched sizes.
+    #[error("mismatched size ({found:?}), expected ({expected:?})")]
+    MismatchedSizes { expected: Size, found: Size },
+    /// Error to represent sizes with an area too large to allocate.
+    #[error("size ({size:?}) area is over the maximum {max}")]
+    InvalidArea { size: Size, max: usize },
+    /// Error to represent malformed or out of range color notations.
+    #[error("invalid color notation {notation:?}")]
+    InvalidColor { notation: String },
+    /// Error to represent tiles with a glyph or color out of a Font or Palette.
+    #[error("unresolved tile at ({coord:?})")]
+    UnresolvedTile { coord: Coord },
 }
 
 impl CommonError {
@@ -27,6 +43,33 @@ impl CommonError {
     pub fn new_invalid_index(index: usize, lenght: usize) -> Self {
         Self::InvalidIndex { index, lenght }
     }
+
+    /// Creates a `InvalidLenght` error.
+    pub fn new_invalid_lenght(lenght: usize, expected: usize) -> Self {
+        Self::InvalidLenght { lenght, expected }
+    }
+
+    /// Creates a `MismatchedSizes` error.
+    pub fn new_mismatched_sizes(expected: Size, found: Size) -> Self {
+        Self::MismatchedSizes { expected, found }
+    }
+
+    /// Creates a `InvalidArea` error.
+    pub fn new_invalid_area(size: Size, max: usize) -> Self {
+        Self::InvalidArea { size, max }
+    }
+
+    /// Creates a `InvalidColor` error.
+    pub fn new_invalid_color(notation: impl Into<String>) -> Self {
+        Self::InvalidColor {
+            notation: notation.into(),
+        }
+    }
+
+    /// Creates a `UnresolvedTile` error.
+    pub fn new_unresolved_tile(coord: Coord) -> Self {
+        Self::UnresolvedTile { coord }
+    }
 }
 
 pub type Result<T> = StdResult<T, CommonError>;
@@ -62,4 +105,64 @@ mod test_super {
             CommonError::InvalidCoord { coord: c, size: s } if c == coord && s == size
         );
     }
+
+    #[test]
+    fn test_commonerror_new_invalid_lenght() {
+        let lenght = 10usize;
+        let expected = 64usize;
+
+        let error = CommonError::new_invalid_lenght(lenght, expected);
+
+        assert_matches!(
+            error,
+            CommonError::InvalidLenght { lenght: l, expected: e } if l == lenght && e == expected
+        );
+    }
+
+    #[test]
+    fn test_commonerror_new_mismatched_sizes() {
+        let expected = Size::new(8, 8);
+        let found = Size::new(4, 4);
+
+        let error = CommonError::new_mismatched_sizes(expected, found);
+
+        assert_matches!(
+            error,
+            CommonError::MismatchedSizes { expected: e, found: f } if e == expected && f == found
+        );
+    }
+
+    #[test]
+    fn test_commonerror_new_invalid_area() {
+        let size = Size::new(usize::MAX, 2);
+        let max = 1024usize;
+
+        let error = CommonError::new_invalid_area(size, max);
+
+        assert_matches!(
+            error,
+            CommonError::InvalidArea { size: s, max: m } if s == size && m == max
+        );
+    }
+
+    #[test]
+    fn test_commonerror_new_invalid_color() {
+        let notation = "rgb(300,0,0)";
+
+        let error = CommonError::new_invalid_color(notation);
+
+        assert_matches!(
+            error,
+            CommonError::InvalidColor { notation: n } if n == notation
+        );
+    }
+
+    #[test]
+    fn test_commonerror_new_unresolved_tile() {
+        let coord = Coord::new(1, 2);
+
+        let error = CommonError::new_unresolved_tile(coord);
+
+        assert_matches!(error, CommonError::UnresolvedTile { coord: c } if c == coord);
+    }
 }
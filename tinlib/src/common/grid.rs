@@ -0,0 +1,295 @@
+//! Grid implementation and manipulation.
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::slice;
+
+use crate::common::coord::{Coord, CoordEnumerate, CoordEnumerateMut, CoordIter};
+use crate::common::error::{CommonError, Result};
+use crate::common::size::Size;
+
+/// A iterator over all grid cells.
+pub type GridIter<'iter, T> = slice::Iter<'iter, T>;
+/// A mutable iterator over all grid cells.
+pub type GridIterMut<'iter, T> = slice::IterMut<'iter, T>;
+/// A enumeration iterator over all grid cells and their coords.
+pub type GridEnumerate<'iter, T> = CoordEnumerate<'iter, T>;
+/// A mutable enumeration iterator over all grid cells and their coords.
+pub type GridEnumerateMut<'iter, T> = CoordEnumerateMut<'iter, T>;
+
+/// The maximum number of cells in a Grid.
+pub const MAX_GRID_AREA: usize = 1 << 20;
+
+/// A two dimensional grid of cells, stored in row-major order.
+///
+/// Cells are stored in a Vec by default. Grids with a fixed size can use an
+/// array instead, see `from_array`, so they don't allocate and are `Copy`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Grid<T, S = Vec<T>> {
+    size: Size,
+    data: S,
+    cell: PhantomData<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a Grid with all cells set to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size area is over `MAX_GRID_AREA`, see `try_new`.
+    pub fn new(size: Size, value: T) -> Self {
+        Self::try_new(size, value).expect("grid area to be at most MAX_GRID_AREA")
+    }
+
+    /// Creates a Grid with all cells set to `value`.
+    ///
+    /// Returns an `InvalidArea` error if the size area overflows or is over
+    /// `MAX_GRID_AREA`, instead of failing on the allocation.
+    pub fn try_new(size: Size, value: T) -> Result<Self> {
+        let area = size
+            .area()
+            .filter(|area| *area <= MAX_GRID_AREA)
+            .ok_or_else(|| CommonError::new_invalid_area(size, MAX_GRID_AREA))?;
+
+        Ok(Self {
+            size,
+            data: vec![value; area],
+            cell: PhantomData,
+        })
+    }
+}
+
+impl<T, const N: usize> Grid<T, [T; N]> {
+    /// Creates a Grid backed by an array of cells, in row-major order.
+    ///
+    /// Returns an `InvalidLenght` error if the size area isn't the array
+    /// lenght.
+    pub fn from_array(size: Size, data: [T; N]) -> Result<Self> {
+        let area = size.area().unwrap_or(usize::MAX);
+        if area != N {
+            return Err(CommonError::new_invalid_lenght(N, area));
+        }
+
+        Ok(Self {
+            size,
+            data,
+            cell: PhantomData,
+        })
+    }
+}
+
+impl<T, S: AsRef<[T]> + AsMut<[T]>> Grid<T, S> {
+    /// Returns the width.
+    pub fn width(&self) -> usize {
+        self.size.width()
+    }
+
+    /// Returns the height.
+    pub fn height(&self) -> usize {
+        self.size.height()
+    }
+
+    /// Returns the Size.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Returns a cell.
+    pub fn get(&self, coord: Coord) -> Result<&T> {
+        if !self.is_coord_valid(coord) {
+            return Err(CommonError::new_invalid_coord(coord, self.size()));
+        }
+
+        let index = self.get_index(coord);
+        Ok(&self.data.as_ref()[index])
+    }
+
+    /// Returns a mutable cell.
+    pub fn get_mut(&mut self, coord: Coord) -> Result<&mut T> {
+        if !self.is_coord_valid(coord) {
+            return Err(CommonError::new_invalid_coord(coord, self.size()));
+        }
+
+        let index = self.get_index(coord);
+        Ok(&mut self.data.as_mut()[index])
+    }
+
+    /// Sets a cell.
+    pub fn set(&mut self, coord: Coord, value: T) -> Result<()> {
+        *self.get_mut(coord)? = value;
+
+        Ok(())
+    }
+
+    /// Returns true if the coord is inside the Grid.
+    pub fn is_coord_valid(&self, coord: Coord) -> bool {
+        coord.x < self.width() && coord.y < self.height()
+    }
+
+    /// Returns all cells as a slice, in row-major order.
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_ref()
+    }
+
+    /// Returns all cells as a mutable slice, in row-major order.
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        self.data.as_mut()
+    }
+
+    /// Returns an iterator over all grid coords.
+    pub fn coords(&self) -> CoordIter {
+        CoordIter::new(self.size())
+    }
+
+    /// Returns an iterator over all grid cells.
+    pub fn iter(&self) -> GridIter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Returns a mutable iterator over all grid cells.
+    pub fn iter_mut(&mut self) -> GridIterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Returns an enumerate iterator over all grid coords and cells.
+    pub fn enumerate(&self) -> GridEnumerate<'_, T> {
+        GridEnumerate::new(self.coords(), self.iter())
+    }
+
+    /// Returns a mutable enumerate iterator over all grid coords and cells.
+    pub fn enumerate_mut(&mut self) -> GridEnumerateMut<'_, T> {
+        GridEnumerateMut::new(self.coords(), self.iter_mut())
+    }
+
+    fn get_index(&self, coord: Coord) -> usize {
+        coord.y * self.width() + coord.x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn test_grid_new() {
+        let grid = Grid::new(Size::new(3, 2), 7u8);
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.size(), Size::new(3, 2));
+        assert_eq!(grid.as_slice(), &[7; 6]);
+    }
+
+    #[test]
+    fn test_grid_try_new() {
+        let result = Grid::try_new(Size::new(1024, 1024), 0u8);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap().as_slice().len(), MAX_GRID_AREA);
+    }
+
+    #[test]
+    fn test_grid_try_new_invalid_area() {
+        for size in [Size::new(usize::MAX, 2), Size::new(1024, 1025)] {
+            let result = Grid::try_new(size, 0u8);
+            assert!(result.is_err());
+            assert_matches!(
+                result.unwrap_err(),
+                CommonError::InvalidArea { size: s, max: m } if s == size && m == MAX_GRID_AREA
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "grid area to be at most MAX_GRID_AREA")]
+    fn test_grid_new_invalid_area() {
+        Grid::new(Size::new(usize::MAX, usize::MAX), 0u8);
+    }
+
+    #[test]
+    fn test_grid_from_array() {
+        let mut grid = Grid::from_array(Size::new(3, 2), [0u8; 6]).unwrap();
+        let copy = grid;
+
+        assert!(grid.set(Coord::new(2, 1), 1).is_ok());
+
+        assert_eq!(grid.size(), Size::new(3, 2));
+        assert_eq!(grid.as_slice(), &[0, 0, 0, 0, 0, 1]);
+        assert_eq!(copy.as_slice(), &[0; 6]);
+    }
+
+    #[test]
+    fn test_grid_from_array_invalid_lenght() {
+        for size in [Size::new(2, 2), Size::new(usize::MAX, 2)] {
+            let result = Grid::from_array(size, [0u8; 6]);
+            assert!(result.is_err());
+            assert_matches!(
+                result.unwrap_err(),
+                CommonError::InvalidLenght { lenght: 6, .. }
+            );
+        }
+    }
+
+    #[test]
+    fn test_grid_get_and_set() {
+        let mut grid = Grid::new(Size::new(3, 2), 0u8);
+
+        assert!(grid.set(Coord::new(2, 0), 1).is_ok());
+        assert!(grid.set(Coord::new(0, 1), 2).is_ok());
+
+        assert_eq!(grid.get(Coord::new(2, 0)).unwrap(), &1);
+        assert_eq!(grid.get(Coord::new(0, 1)).unwrap(), &2);
+        assert_eq!(grid.as_slice(), &[0, 0, 1, 2, 0, 0]);
+    }
+
+    #[test]
+    fn test_grid_get_mut() {
+        let mut grid = Grid::new(Size::new(3, 2), 0u8);
+
+        *grid.get_mut(Coord::new(1, 1)).unwrap() = 5;
+
+        assert_eq!(grid.get(Coord::new(1, 1)).unwrap(), &5);
+    }
+
+    #[test]
+    fn test_grid_invalid_coord() {
+        let mut grid = Grid::new(Size::new(3, 2), 0u8);
+        let coord = Coord::new(0, 2);
+
+        let result = grid.get(coord);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidCoord { coord: c, size: s } if c == coord && s == Size::new(3, 2)
+        );
+
+        let result = grid.set(Coord::new(3, 0), 1);
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CommonError::InvalidCoord { .. });
+    }
+
+    #[test]
+    fn test_grid_iter() {
+        let mut grid = Grid::new(Size::new(3, 2), 0u8);
+        for (index, cell) in grid.iter_mut().enumerate() {
+            *cell = index as u8;
+        }
+
+        let cells: Vec<u8> = grid.iter().copied().collect();
+        assert_eq!(cells, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_grid_enumerate() {
+        let mut grid = Grid::new(Size::new(3, 2), 0u8);
+        for (coord, cell) in grid.enumerate_mut() {
+            *cell = (coord.y * 10 + coord.x) as u8;
+        }
+
+        for (coord, cell) in grid.enumerate() {
+            assert_eq!(*cell as usize, coord.y * 10 + coord.x);
+        }
+        assert_eq!(grid.as_slice(), &[0, 1, 2, 10, 11, 12]);
+    }
+}
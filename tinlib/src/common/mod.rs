@@ -1,8 +1,16 @@
 //! Common utilities.
 mod coord;
 mod error;
+mod grid;
+mod rect;
 mod size;
 
-pub use crate::common::coord::{Coord, CoordEnumerate, CoordEnumerateMut, CoordIter};
+pub use crate::common::coord::{
+    coord_from_signed, Coord, CoordEnumerate, CoordEnumerateMut, CoordIter, LineIter,
+};
 pub use crate::common::error::{CommonError, Result};
+pub use crate::common::grid::{
+    Grid, GridEnumerate, GridEnumerateMut, GridIter, GridIterMut, MAX_GRID_AREA,
+};
+pub use crate::common::rect::Rect;
 pub use crate::common::size::Size;
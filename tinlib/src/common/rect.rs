@@ -0,0 +1,115 @@
+//! Rect implementation and manipulation.
+use crate::common::coord::{Coord, CoordIter};
+use crate::common::size::Size;
+
+/// A Rect implementation with an origin Coord and a Size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rect {
+    origin: Coord,
+    size: Size,
+}
+
+impl Rect {
+    /// Creates a new Rect.
+    pub fn new(origin: Coord, size: Size) -> Self {
+        Self { origin, size }
+    }
+
+    /// Returns the origin, the top-left Coord.
+    pub fn origin(&self) -> Coord {
+        self.origin
+    }
+
+    /// Returns the size.
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Returns true if the coord is inside the Rect.
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.x >= self.origin.x
+            && coord.y >= self.origin.y
+            && coord.x - self.origin.x < self.size.width()
+            && coord.y - self.origin.y < self.size.height()
+    }
+
+    /// Returns the smallest Rect enclosing both Rects.
+    pub fn union(&self, other: Rect) -> Rect {
+        let left = self.origin.x.min(other.origin.x);
+        let top = self.origin.y.min(other.origin.y);
+        let right = (self.origin.x + self.size.width()).max(other.origin.x + other.size.width());
+        let bottom = (self.origin.y + self.size.height()).max(other.origin.y + other.size.height());
+
+        Rect::new(Coord::new(left, top), Size::new(right - left, bottom - top))
+    }
+
+    /// Returns an iterator over all Coords inside the Rect, in row-major
+    /// order.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let origin = self.origin;
+
+        CoordIter::new(self.size)
+            .map(move |coord| Coord::new(origin.x + coord.x, origin.y + coord.y))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_rect_new() {
+        let rect = Rect::new(Coord::new(2, 3), Size::new(4, 5));
+
+        assert_eq!(rect.origin(), Coord::new(2, 3));
+        assert_eq!(rect.size(), Size::new(4, 5));
+    }
+
+    #[test]
+    fn test_rect_contains() {
+        let rect = Rect::new(Coord::new(2, 3), Size::new(2, 2));
+
+        assert!(rect.contains(Coord::new(2, 3)));
+        assert!(rect.contains(Coord::new(3, 4)));
+        assert!(!rect.contains(Coord::new(1, 3)));
+        assert!(!rect.contains(Coord::new(4, 3)));
+        assert!(!rect.contains(Coord::new(3, 5)));
+        assert!(!Rect::new(Coord::new(2, 3), Size::new(0, 0)).contains(Coord::new(2, 3)));
+    }
+
+    #[test]
+    fn test_rect_union() {
+        let a = Rect::new(Coord::new(2, 3), Size::new(2, 2));
+        let b = Rect::new(Coord::new(10, 1), Size::new(1, 1));
+
+        let expected = Rect::new(Coord::new(2, 1), Size::new(9, 4));
+        assert_eq!(a.union(b), expected);
+        assert_eq!(b.union(a), expected);
+        assert_eq!(a.union(a), a);
+    }
+
+    #[test]
+    fn test_rect_coords() {
+        let rect = Rect::new(Coord::new(2, 3), Size::new(2, 2));
+
+        let coords: Vec<Coord> = rect.coords().collect();
+        assert_eq!(
+            coords,
+            [
+                Coord::new(2, 3),
+                Coord::new(3, 3),
+                Coord::new(2, 4),
+                Coord::new(3, 4)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rect_coords_empty() {
+        let rect = Rect::new(Coord::new(2, 3), Size::new(0, 2));
+
+        assert_eq!(rect.coords().count(), 0);
+    }
+}
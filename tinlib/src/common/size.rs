@@ -22,6 +22,31 @@ impl Size {
     pub fn height(&self) -> usize {
         self.height
     }
+
+    /// Returns the area, or `None` if it overflows `usize`.
+    pub fn area(&self) -> Option<usize> {
+        self.width.checked_mul(self.height)
+    }
+
+    /// Returns how many whole tiles fit in each dimension.
+    ///
+    /// A zero tile dimension fits no tiles.
+    pub fn tiles(&self, tile: Size) -> Size {
+        Size::new(
+            self.width.checked_div(tile.width).unwrap_or(0),
+            self.height.checked_div(tile.height).unwrap_or(0),
+        )
+    }
+
+    /// Returns the size left over in each dimension after the whole tiles.
+    ///
+    /// A zero tile dimension leaves the whole dimension over.
+    pub fn remainder(&self, tile: Size) -> Size {
+        Size::new(
+            self.width.checked_rem(tile.width).unwrap_or(self.width),
+            self.height.checked_rem(tile.height).unwrap_or(self.height),
+        )
+    }
 }
 
 impl From<(usize, usize)> for Size {
@@ -56,6 +81,32 @@ mod test {
         assert_eq!(size.height(), 48);
     }
 
+    #[test]
+    fn test_size_area() {
+        assert_eq!(Size::new(3, 4).area(), Some(12));
+        assert_eq!(Size::new(0, 4).area(), Some(0));
+        assert_eq!(Size::new(usize::MAX, 2).area(), None);
+    }
+
+    #[test]
+    fn test_size_tiles_and_remainder() {
+        let size = Size::new(640, 384);
+
+        assert_eq!(size.tiles(Size::new(16, 16)), Size::new(40, 24));
+        assert_eq!(size.remainder(Size::new(16, 16)), Size::new(0, 0));
+
+        assert_eq!(size.tiles(Size::new(100, 100)), Size::new(6, 3));
+        assert_eq!(size.remainder(Size::new(100, 100)), Size::new(40, 84));
+    }
+
+    #[test]
+    fn test_size_tiles_and_remainder_zero_tile() {
+        let size = Size::new(640, 384);
+
+        assert_eq!(size.tiles(Size::new(0, 16)), Size::new(0, 24));
+        assert_eq!(size.remainder(Size::new(0, 16)), Size::new(640, 0));
+    }
+
     #[test]
     fn test_size_from_tuple() {
         let tuple = (80usize, 48usize);
@@ -0,0 +1,148 @@
+//! PaletteAnimator implementation and manipulation.
+use core::ops::Range;
+
+use crate::common::{CommonError, Result};
+use crate::graphic::palette::Palette;
+
+/// A palette cycling animation, for effects like flowing water or lava.
+///
+/// Every `interval` ticks the colors in the range rotate one position to the
+/// left, so each color moves to the previous index and the first one wraps to
+/// the end. Colors outside the range stay put.
+#[derive(Debug, Clone)]
+pub struct PaletteAnimator {
+    palette: Palette,
+    range: Range<usize>,
+    interval: u32,
+    ticks: u32,
+}
+
+impl PaletteAnimator {
+    /// Creates a PaletteAnimator cycling the colors of `range` every
+    /// `interval` ticks.
+    ///
+    /// An interval of `0` cycles every tick, like `1`. Returns an
+    /// `InvalidIndex` error if the range doesn't fit in the palette.
+    pub fn new(palette: Palette, range: Range<usize>, interval: u32) -> Result<Self> {
+        if range.start > range.end || range.end > palette.lenght() {
+            return Err(CommonError::new_invalid_index(range.end, palette.lenght()));
+        }
+
+        Ok(Self {
+            palette,
+            range,
+            interval: interval.max(1),
+            ticks: 0,
+        })
+    }
+
+    /// Returns the animated palette.
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    /// Returns the cycled range.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// Returns the number of ticks between rotations.
+    pub fn interval(&self) -> u32 {
+        self.interval
+    }
+
+    /// Advances the animation a tick, rotating the range on each interval.
+    ///
+    /// Returns true if the palette changed.
+    pub fn tick(&mut self) -> bool {
+        self.ticks += 1;
+        if self.ticks < self.interval {
+            return false;
+        }
+
+        self.ticks = 0;
+        let colors = &mut self.palette.colors[self.range.clone()];
+        if colors.len() < 2 {
+            return false;
+        }
+        colors.rotate_left(1);
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use crate::graphic::Color;
+
+    use super::*;
+
+    fn numbered_palette() -> Palette {
+        let mut palette = Palette::default();
+        for (index, color) in palette.iter_mut().enumerate() {
+            *color = Color::new(index as u8, 0, 0);
+        }
+
+        palette
+    }
+
+    fn reds(palette: &Palette) -> [u8; 16] {
+        let mut result = [0; 16];
+        for (red, color) in result.iter_mut().zip(palette.iter()) {
+            *red = color.red();
+        }
+
+        result
+    }
+
+    #[test]
+    fn test_paletteanimator_tick() {
+        let mut animator = PaletteAnimator::new(numbered_palette(), 4..8, 2).unwrap();
+
+        assert!(!animator.tick());
+        assert_eq!(reds(animator.palette()), reds(&numbered_palette()));
+
+        assert!(animator.tick());
+        assert_eq!(
+            reds(animator.palette()),
+            [0, 1, 2, 3, 5, 6, 7, 4, 8, 9, 10, 11, 12, 13, 14, 15]
+        );
+
+        for _ in 0..6 {
+            animator.tick();
+        }
+        assert_eq!(reds(animator.palette()), reds(&numbered_palette()));
+    }
+
+    #[test]
+    fn test_paletteanimator_zero_interval() {
+        let mut animator = PaletteAnimator::new(numbered_palette(), 0..3, 0).unwrap();
+
+        assert_eq!(animator.interval(), 1);
+        assert!(animator.tick());
+        assert_eq!(
+            reds(animator.palette()),
+            [1, 2, 0, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15]
+        );
+    }
+
+    #[test]
+    fn test_paletteanimator_single_color_range() {
+        let mut animator = PaletteAnimator::new(numbered_palette(), 3..4, 1).unwrap();
+
+        assert!(!animator.tick());
+        assert_eq!(reds(animator.palette()), reds(&numbered_palette()));
+    }
+
+    #[test]
+    fn test_paletteanimator_invalid_range() {
+        let result = PaletteAnimator::new(Palette::default(), 10..17, 1);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidIndex { index: i, lenght: l } if i == 17 && l == 16
+        );
+    }
+}
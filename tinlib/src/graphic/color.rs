@@ -1,7 +1,22 @@
 //! Color implementation and manipulation.
+use core::mem;
+use core::slice;
+
+use crate::common::{CommonError, Result};
+
+/// Number of bytes in a Color.
+const COLOR_BYTES: usize = 3;
+
+// A Color must be exactly its channel bytes, without any padding.
+const _: () = assert!(mem::size_of::<Color>() == COLOR_BYTES);
+const _: () = assert!(mem::align_of::<Color>() == 1);
 
 /// A color representation with red, green and blue values.
+///
+/// The layout is `#[repr(C)]`, so a Color is exactly 3 bytes in red, green,
+/// blue order.
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[repr(C)]
 pub struct Color {
     red: u8,
     green: u8,
@@ -10,12 +25,12 @@ pub struct Color {
 
 impl Color {
     /// Creates a Color with red, green and blue values.
-    pub fn new(red: u8, green: u8, blue: u8) -> Self {
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
         Self { red, green, blue }
     }
 
     /// Creates a Color from a hexadecimal value.
-    pub fn new_from_hex(value: u32) -> Self {
+    pub const fn new_from_hex(value: u32) -> Self {
         Self {
             red: ((value & 0x00ff_0000) >> 16) as u8,
             green: ((value & 0x0000_ff00) >> 8) as u8,
@@ -23,6 +38,36 @@ impl Color {
         }
     }
 
+    /// Creates a Color from the CSS `rgb(r, g, b)` notation.
+    ///
+    /// Channels are integers from 0 to 255, and whitespace is allowed around
+    /// them and the notation. Returns an `InvalidColor` error for malformed
+    /// notations or out of range channels.
+    pub fn from_css(notation: &str) -> Result<Self> {
+        let error = || CommonError::new_invalid_color(notation);
+
+        let channels = notation
+            .trim()
+            .strip_prefix("rgb(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(error)?;
+
+        let mut values = [0u8; 3];
+        let mut parts = channels.split(',');
+        for value in values.iter_mut() {
+            let part = parts.next().ok_or_else(error)?.trim();
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(error());
+            }
+            *value = part.parse().map_err(|_| error())?;
+        }
+        if parts.next().is_some() {
+            return Err(error());
+        }
+
+        Ok(Self::from(values))
+    }
+
     /// Get red value.
     ///
     pub fn red(self) -> u8 {
@@ -38,6 +83,107 @@ impl Color {
     pub fn blue(self) -> u8 {
         self.blue
     }
+
+    /// Returns the color channels as bytes, in red, green, blue order.
+    pub fn as_bytes(&self) -> &[u8] {
+        // SAFETY: Color is `repr(C)` with three `u8` fields and no padding.
+        unsafe { slice::from_raw_parts(self as *const Color as *const u8, COLOR_BYTES) }
+    }
+
+    /// Returns a slice of colors as bytes, in red, green, blue order per color.
+    pub fn slice_as_bytes(colors: &[Color]) -> &[u8] {
+        // SAFETY: Color is `repr(C)` with three `u8` fields, no padding and
+        // alignment 1, so a slice of colors is a contiguous slice of bytes.
+        unsafe { slice::from_raw_parts(colors.as_ptr() as *const u8, colors.len() * COLOR_BYTES) }
+    }
+
+    /// Returns the per channel sum of two colors, saturating at 255.
+    pub fn saturating_add(self, other: Color) -> Self {
+        Self {
+            red: self.red.saturating_add(other.red),
+            green: self.green.saturating_add(other.green),
+            blue: self.blue.saturating_add(other.blue),
+        }
+    }
+
+    /// Returns the per channel difference of two colors, saturating at 0.
+    pub fn saturating_sub(self, other: Color) -> Self {
+        Self {
+            red: self.red.saturating_sub(other.red),
+            green: self.green.saturating_sub(other.green),
+            blue: self.blue.saturating_sub(other.blue),
+        }
+    }
+
+    /// Returns the Color with every channel inverted.
+    pub fn invert(self) -> Self {
+        Self {
+            red: u8::MAX - self.red,
+            green: u8::MAX - self.green,
+            blue: u8::MAX - self.blue,
+        }
+    }
+
+    /// Returns the Color as gray, using the Rec. 601 luma weights.
+    pub fn to_grayscale(self) -> Self {
+        let luma = 0.299 * self.red as f32 + 0.587 * self.green as f32 + 0.114 * self.blue as f32;
+        let value = (luma + 0.5).min(255.0) as u8;
+
+        Self::new(value, value, value)
+    }
+
+    /// Returns the Color with the standard sepia filter applied.
+    pub fn sepia(self) -> Self {
+        let (r, g, b) = (self.red as f32, self.green as f32, self.blue as f32);
+        let channel = |value: f32| (value + 0.5).min(255.0) as u8;
+
+        Self {
+            red: channel(0.393 * r + 0.769 * g + 0.189 * b),
+            green: channel(0.349 * r + 0.686 * g + 0.168 * b),
+            blue: channel(0.272 * r + 0.534 * g + 0.131 * b),
+        }
+    }
+
+    /// Creates a Color linearly interpolated between this and another color.
+    ///
+    /// The factor `t` is clamped to `[0.0, 1.0]`, where `0.0` returns this color
+    /// and `1.0` returns `other`.
+    pub fn lerp(self, other: Color, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        // Rounds half up, as the interpolated channel is never negative.
+        let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t + 0.5) as u8;
+
+        Self {
+            red: channel(self.red, other.red),
+            green: channel(self.green, other.green),
+            blue: channel(self.blue, other.blue),
+        }
+    }
+
+    /// Returns the WCAG relative luminance, from `0.0` for black to `1.0` for white.
+    #[cfg(feature = "std")]
+    pub fn relative_luminance(self) -> f32 {
+        let channel = |value: u8| {
+            let value = value as f32 / 255.0;
+            if value <= 0.03928 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        0.2126 * channel(self.red) + 0.7152 * channel(self.green) + 0.0722 * channel(self.blue)
+    }
+
+    /// Returns the WCAG contrast ratio to another color, from `1.0` to `21.0`.
+    #[cfg(feature = "std")]
+    pub fn contrast_ratio(self, other: Color) -> f32 {
+        let a = self.relative_luminance();
+        let b = other.relative_luminance();
+        let (lighter, darker) = if a > b { (a, b) } else { (b, a) };
+
+        (lighter + 0.05) / (darker + 0.05)
+    }
 }
 
 impl From<(u8, u8, u8)> for Color {
@@ -54,6 +200,8 @@ impl From<[u8; 3]> for Color {
 
 #[cfg(test)]
 mod tests {
+    use assert_matches::assert_matches;
+
     use super::*;
 
     #[test]
@@ -65,6 +213,15 @@ mod tests {
         assert_eq!(color.blue, 3);
     }
 
+    #[test]
+    fn test_color_new_in_static() {
+        static BLACK: Color = Color::new(0, 0, 0);
+        static GREEN: Color = Color::new_from_hex(0x7bc950);
+
+        assert_eq!(BLACK, Color::default());
+        assert_eq!(GREEN, Color::new(123, 201, 80));
+    }
+
     #[test]
     fn test_color_new_from_hex() {
         let color = Color::new_from_hex(0x7bc950);
@@ -74,6 +231,46 @@ mod tests {
         assert_eq!(color.blue, 80);
     }
 
+    #[test]
+    fn test_color_from_css() {
+        let data = [
+            ("rgb(123, 201, 80)", Color::new(123, 201, 80)),
+            ("rgb(0,0,0)", Color::new(0, 0, 0)),
+            (" rgb( 255 ,255,  255 ) ", Color::new(255, 255, 255)),
+        ];
+
+        for (notation, expected) in data.iter() {
+            let result = Color::from_css(notation);
+            assert!(result.is_ok());
+            assert_eq!(result.unwrap(), *expected);
+        }
+    }
+
+    #[test]
+    fn test_color_from_css_invalid() {
+        let data = [
+            "rgb(300,0,0)",
+            "rgb(-1,0,0)",
+            "rgb(+1,0,0)",
+            "rgb(1,2)",
+            "rgb(1,2,3,4)",
+            "rgb(1,,3)",
+            "rgb(1,2,3",
+            "rgba(1,2,3)",
+            "#ff0000",
+            "",
+        ];
+
+        for notation in data.iter() {
+            let result = Color::from_css(notation);
+            assert!(result.is_err());
+            assert_matches!(
+                result.unwrap_err(),
+                CommonError::InvalidColor { notation: n } if n == *notation
+            );
+        }
+    }
+
     #[test]
     fn test_color_red_green_blue() {
         let color = Color::new(1, 2, 3);
@@ -83,6 +280,112 @@ mod tests {
         assert_eq!(color.blue(), 3);
     }
 
+    #[test]
+    fn test_color_as_bytes() {
+        let color = Color::new(1, 2, 3);
+
+        assert_eq!(color.as_bytes(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_color_slice_as_bytes() {
+        let colors = [Color::new(1, 2, 3), Color::new(4, 5, 6)];
+
+        let bytes = Color::slice_as_bytes(&colors);
+        assert_eq!(bytes.len(), 6);
+        assert_eq!(bytes, &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_color_saturating_add() {
+        let color = Color::new(200, 200, 200);
+
+        assert_eq!(
+            color.saturating_add(Color::new(100, 100, 100)),
+            Color::new(255, 255, 255)
+        );
+        assert_eq!(
+            color.saturating_add(Color::new(1, 2, 3)),
+            Color::new(201, 202, 203)
+        );
+    }
+
+    #[test]
+    fn test_color_saturating_sub() {
+        let color = Color::new(100, 100, 100);
+
+        assert_eq!(
+            color.saturating_sub(Color::new(200, 50, 100)),
+            Color::new(0, 50, 0)
+        );
+    }
+
+    #[test]
+    fn test_color_to_grayscale() {
+        assert_eq!(Color::new(255, 0, 0).to_grayscale(), Color::new(76, 76, 76));
+        assert_eq!(
+            Color::new(0, 255, 0).to_grayscale(),
+            Color::new(150, 150, 150)
+        );
+        assert_eq!(Color::new(0, 0, 255).to_grayscale(), Color::new(29, 29, 29));
+        assert_eq!(
+            Color::new(255, 255, 255).to_grayscale(),
+            Color::new(255, 255, 255)
+        );
+    }
+
+    #[test]
+    fn test_color_sepia() {
+        let color = Color::new(128, 128, 128);
+
+        assert_eq!(color.sepia(), Color::new(173, 154, 120));
+        assert_eq!(Color::new(0, 0, 0).sepia(), Color::new(0, 0, 0));
+    }
+
+    #[test]
+    fn test_color_sepia_saturates() {
+        let color = Color::new(255, 255, 255);
+
+        assert_eq!(color.sepia(), Color::new(255, 255, 239));
+    }
+
+    #[test]
+    fn test_color_invert() {
+        let black = Color::new(0, 0, 0);
+        let white = Color::new(255, 255, 255);
+        let color = Color::new(10, 128, 200);
+
+        assert_eq!(black.invert(), white);
+        assert_eq!(white.invert(), black);
+        assert_eq!(color.invert(), Color::new(245, 127, 55));
+        assert_eq!(color.invert().invert(), color);
+    }
+
+    #[test]
+    fn test_color_lerp() {
+        let start = Color::new(0, 100, 255);
+        let end = Color::new(255, 200, 55);
+
+        assert_eq!(start.lerp(end, 0.0), start);
+        assert_eq!(start.lerp(end, 1.0), end);
+        assert_eq!(start.lerp(end, 0.5), Color::new(128, 150, 155));
+        assert_eq!(start.lerp(end, -1.0), start);
+        assert_eq!(start.lerp(end, 2.0), end);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_color_contrast_ratio() {
+        let black = Color::new(0, 0, 0);
+        let white = Color::new(255, 255, 255);
+        let gray = Color::new(119, 119, 119);
+
+        assert!((black.contrast_ratio(white) - 21.0).abs() < 1e-4);
+        assert!((white.contrast_ratio(black) - 21.0).abs() < 1e-4);
+        assert_eq!(gray.contrast_ratio(gray), 1.0);
+        assert!((white.contrast_ratio(gray) - 4.48).abs() < 0.01);
+    }
+
     #[test]
     fn test_color_from_tuple() {
         let tuple = (1u8, 2u8, 3u8);
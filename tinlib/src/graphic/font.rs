@@ -1,13 +1,27 @@
 //! Font implementation and manipulation.
-use std::fmt;
-use std::slice;
-
-use crate::common::{CommonError, Result};
-use crate::graphic::glyph::Glyph;
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::slice;
+
+#[cfg(feature = "image")]
+use crate::common::Coord;
+use crate::common::{CommonError, Result, Size};
+#[cfg(feature = "image")]
+use crate::graphic::glyph::GlyphPixel;
+use crate::graphic::glyph::{Glyph, GLYPH_BYTES, GLYPH_HEIGHT};
 
 /// Number of Glyphs in a Font.
 const GLYPHS_IN_FONT: usize = 256;
 
+/// Gray level from which image pixels are solid.
+#[cfg(feature = "image")]
+const IMAGE_SOLID_THRESHOLD: u8 = 128;
+
+/// Number of bytes in a serialized Font.
+pub const FONT_BYTES: usize = GLYPHS_IN_FONT * GLYPH_BYTES;
+
 /// A iterator over all font glyphs.
 pub type FontGlyphIter<'iter> = slice::Iter<'iter, Glyph>;
 /// A mutable iterator over all font glyphs.
@@ -18,9 +32,82 @@ pub type FontGlyphIterMut<'iter> = slice::IterMut<'iter, Glyph>;
 pub struct Font {
     /// Font's glyphs.
     pub glyphs: [Glyph; GLYPHS_IN_FONT],
+    ascent: usize,
+    descent: usize,
 }
 
 impl Font {
+    /// Creates a Font of empty glyphs with the given glyph size.
+    ///
+    /// Returns an error for any size `Glyph::try_new` rejects.
+    pub fn try_new(glyph_size: Size) -> Result<Font> {
+        let glyph = Glyph::try_new(glyph_size)?;
+
+        Ok(Self::filled(glyph))
+    }
+
+    /// Creates a Font from bytes.
+    ///
+    /// The data is every glyph in index order, each one as described in
+    /// `Glyph::from_bytes`, for a total of 16384 bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Font> {
+        if bytes.len() != FONT_BYTES {
+            return Err(CommonError::new_invalid_lenght(bytes.len(), FONT_BYTES));
+        }
+
+        let mut font = Font::default();
+        for (glyph, chunk) in font.glyphs.iter_mut().zip(bytes.chunks(GLYPH_BYTES)) {
+            *glyph = Glyph::from_bytes(chunk)?;
+        }
+
+        Ok(font)
+    }
+
+    /// Creates a Font from a grayscale atlas image.
+    ///
+    /// The atlas is a grid of `columns` glyphs per row, read in row-major order
+    /// into the font glyphs. Pixels with a gray level of 128 or more are solid.
+    #[cfg(feature = "image")]
+    pub fn from_image(img: &image::GrayImage, glyph_size: Size, columns: usize) -> Result<Font> {
+        let mut font = Font::try_new(glyph_size)?;
+
+        let image_size = Size::new(img.width() as usize, img.height() as usize);
+        let rows = image_size.height() / glyph_size.height();
+        let grid_size = Size::new(columns * glyph_size.width(), rows * glyph_size.height());
+        if columns == 0 || image_size != grid_size {
+            return Err(CommonError::new_mismatched_sizes(grid_size, image_size));
+        }
+
+        let count = columns * rows;
+        if count > font.lenght() {
+            return Err(CommonError::new_invalid_lenght(count, font.lenght()));
+        }
+
+        for (index, glyph) in font.glyphs.iter_mut().take(count).enumerate() {
+            let origin = Coord::new(
+                (index % columns) * glyph_size.width(),
+                (index / columns) * glyph_size.height(),
+            );
+
+            for (coord, pixel) in glyph.enumerate_mut() {
+                let luma = img.get_pixel((origin.x + coord.x) as u32, (origin.y + coord.y) as u32);
+                if luma.0[0] >= IMAGE_SOLID_THRESHOLD {
+                    *pixel = GlyphPixel::Solid;
+                }
+            }
+        }
+
+        Ok(font)
+    }
+
+    /// Returns the Font as bytes, in the format described in `from_bytes`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.glyphs
+            .iter()
+            .flat_map(|glyph| glyph.to_bytes())
+            .collect()
+    }
+
     /// Returns the lenght.
     pub fn lenght(&self) -> usize {
         GLYPHS_IN_FONT
@@ -28,11 +115,16 @@ impl Font {
 
     /// Returns a glyph.
     pub fn get_glyph(&self, index: usize) -> Result<Glyph> {
+        self.glyph(index).copied()
+    }
+
+    /// Returns a glyph reference, without copying it.
+    pub fn glyph(&self, index: usize) -> Result<&Glyph> {
         if !self.is_index_valid(index) {
             return Err(CommonError::new_invalid_index(index, self.lenght()));
         }
 
-        Ok(self.glyphs[index])
+        Ok(&self.glyphs[index])
     }
 
     /// Sets a glyph.
@@ -46,16 +138,110 @@ impl Font {
         Ok(())
     }
 
+    /// Returns the horizontal advance of a glyph for proportional text.
+    ///
+    /// The advance is the width of the glyph's solid pixels plus 1 pixel of
+    /// spacing, or the full glyph width for empty glyphs.
+    pub fn advance_width(&self, index: usize) -> Result<usize> {
+        let glyph = self.glyph(index)?;
+
+        Ok(match glyph.bounding_box() {
+            Some((_, size)) => size.width() + 1,
+            None => glyph.width(),
+        })
+    }
+
+    /// Returns the indexes of the glyphs that differ from another Font.
+    ///
+    /// Fonts always have 256 glyphs, so every glyph is compared. Returns a
+    /// `MismatchedSizes` error if two glyphs at the same index have different
+    /// sizes, as their pixels can't be compared.
+    pub fn changed_glyphs(&self, other: &Font) -> Result<Vec<usize>> {
+        let mut changed = Vec::new();
+        for (index, (a, b)) in self.iter().zip(other.iter()).enumerate() {
+            if a.size() != b.size() {
+                return Err(CommonError::new_mismatched_sizes(a.size(), b.size()));
+            }
+            if a != b {
+                changed.push(index);
+            }
+        }
+
+        Ok(changed)
+    }
+
+    /// Returns a Font with only the glyphs at `indices`, packed from index 0
+    /// in the given order, and a remap from their old to their new indexes.
+    ///
+    /// Repeated indices are kept once, and the remaining glyphs are empty.
+    /// The metrics are kept.
+    pub fn subset(&self, indices: &[usize]) -> Result<(Font, BTreeMap<usize, usize>)> {
+        let mut font = Font {
+            ascent: self.ascent,
+            descent: self.descent,
+            ..Font::default()
+        };
+        let mut remap = BTreeMap::new();
+
+        for index in indices.iter().copied() {
+            let glyph = self.glyph(index)?;
+
+            if !remap.contains_key(&index) {
+                let new_index = remap.len();
+                font.glyphs[new_index] = *glyph;
+                remap.insert(index, new_index);
+            }
+        }
+
+        Ok((font, remap))
+    }
+
+    /// Returns the ascent, the pixels from the top of the glyphs to the
+    /// baseline.
+    ///
+    /// Defaults to the full glyph height, so the baseline is at the bottom of
+    /// the glyphs.
+    pub fn ascent(&self) -> usize {
+        self.ascent
+    }
+
+    /// Sets the ascent.
+    pub fn set_ascent(&mut self, ascent: usize) {
+        self.ascent = ascent;
+    }
+
+    /// Returns the descent, the pixels below the baseline. Defaults to `0`.
+    pub fn descent(&self) -> usize {
+        self.descent
+    }
+
+    /// Sets the descent.
+    pub fn set_descent(&mut self, descent: usize) {
+        self.descent = descent;
+    }
+
     /// Returns an iterator over all font glyphs.
-    pub fn iter(&self) -> FontGlyphIter {
+    pub fn iter(&self) -> FontGlyphIter<'_> {
         self.glyphs.iter()
     }
 
     /// Returns a mutable iterator over all font glyphs.
-    pub fn iter_mut(&mut self) -> FontGlyphIterMut {
+    pub fn iter_mut(&mut self) -> FontGlyphIterMut<'_> {
         self.glyphs.iter_mut()
     }
 
+    fn filled(glyph: Glyph) -> Self {
+        let glyphs = vec![glyph; GLYPHS_IN_FONT]
+            .try_into()
+            .expect("glyphs vector to have the font lenght");
+
+        Self {
+            glyphs,
+            ascent: GLYPH_HEIGHT,
+            descent: 0,
+        }
+    }
+
     fn is_index_valid(&self, index: usize) -> bool {
         index < self.lenght()
     }
@@ -64,9 +250,7 @@ impl Font {
 impl Default for Font {
     /// Creates a Font with default empty glyphs.
     fn default() -> Self {
-        Self {
-            glyphs: [Glyph::default(); GLYPHS_IN_FONT],
-        }
+        Self::filled(Glyph::default())
     }
 }
 
@@ -87,12 +271,140 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_font_metrics() {
+        let mut font = Font::default();
+        assert_eq!(font.ascent(), 8);
+        assert_eq!(font.descent(), 0);
+
+        font.set_ascent(6);
+        font.set_descent(2);
+        assert_eq!(font.ascent(), 6);
+        assert_eq!(font.descent(), 2);
+
+        let (subset, _) = font.subset(&[1]).unwrap();
+        assert_eq!(subset.ascent(), 6);
+        assert_eq!(subset.descent(), 2);
+    }
+
+    #[test]
+    fn test_font_try_new() {
+        let result = Font::try_new(Size::new(8, 8));
+        assert!(result.is_ok());
+        assert!(result
+            .unwrap()
+            .changed_glyphs(&Font::default())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_font_try_new_zero_size() {
+        let result = Font::try_new(Size::new(0, 0));
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::MismatchedSizes { expected: e, found: f } if e == Size::new(8, 8) && f == Size::new(0, 0)
+        );
+    }
+
     #[test]
     fn test_font_default() {
         let font = Font::default();
         assert_eq!(font.glyphs.len(), GLYPHS_IN_FONT);
     }
 
+    #[test]
+    fn test_font_to_bytes() {
+        let font = Font::default();
+
+        let bytes = font.to_bytes();
+        assert_eq!(bytes.len(), 16384);
+        assert_eq!(bytes.len(), FONT_BYTES);
+
+        let result = Font::from_bytes(&bytes);
+        assert!(result.is_ok());
+        assert!(result.unwrap().changed_glyphs(&font).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_font_from_bytes() {
+        let mut bytes = vec![0u8; FONT_BYTES];
+        bytes[65 * GLYPH_BYTES] = 1;
+
+        let result = Font::from_bytes(&bytes);
+        assert!(result.is_ok());
+
+        let font = result.unwrap();
+        assert_eq!(font.changed_glyphs(&Font::default()).unwrap(), vec![65]);
+        assert_eq!(font.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_font_from_bytes_invalid_lenght() {
+        let bytes = vec![0u8; 12];
+
+        let result = Font::from_bytes(&bytes);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidLenght { lenght: l, expected: e } if l == 12 && e == FONT_BYTES
+        );
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_font_from_image() {
+        let glyph_size = Size::new(8, 8);
+        let mut img = image::GrayImage::new(16, 24);
+        // glyph 3 is at column 1, row 1.
+        img.put_pixel(8 + 2, 8 + 5, image::Luma([200]));
+        img.put_pixel(8 + 3, 8 + 5, image::Luma([127]));
+
+        let result = Font::from_image(&img, glyph_size, 2);
+        assert!(result.is_ok());
+
+        let font = result.unwrap();
+        let glyph = font.get_glyph(3).unwrap();
+        assert_eq!(
+            glyph.get_pixel(Coord::new(2, 5)).unwrap(),
+            GlyphPixel::Solid
+        );
+        assert_eq!(
+            glyph.get_pixel(Coord::new(3, 5)).unwrap(),
+            GlyphPixel::Empty
+        );
+        assert_eq!(font.changed_glyphs(&Font::default()).unwrap(), vec![3]);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_font_from_image_invalid_grid() {
+        let glyph_size = Size::new(8, 8);
+        let img = image::GrayImage::new(16, 20);
+
+        let result = Font::from_image(&img, glyph_size, 2);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::MismatchedSizes { expected: e, found: f } if e == Size::new(16, 16) && f == Size::new(16, 20)
+        );
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_font_from_image_invalid_glyph_size() {
+        let glyph_size = Size::new(16, 16);
+        let img = image::GrayImage::new(32, 32);
+
+        let result = Font::from_image(&img, glyph_size, 2);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::MismatchedSizes { expected: e, found: f } if e == Size::new(8, 8) && f == glyph_size
+        );
+    }
+
     #[test]
     fn test_font_len() {
         let font = Font::default();
@@ -122,6 +434,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_font_glyph() {
+        let mut font = Font::default();
+        font.glyphs[3]
+            .set_pixel(Coord::new(1, 2), GlyphPixel::Solid)
+            .unwrap();
+
+        let result = font.glyph(3);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), &font.glyphs[3]);
+    }
+
+    #[test]
+    fn test_font_glyph_invalid_index() {
+        let font = Font::default();
+        let index = 256usize;
+
+        let result = font.glyph(index);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidIndex { index: i, lenght: l } if i == index && l == font.lenght()
+        );
+    }
+
     #[test]
     fn test_font_set_glyph() {
         let mut font = Font::default();
@@ -151,6 +488,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_font_advance_width() {
+        let mut font = Font::default();
+        font.glyphs[1]
+            .set_pixel(Coord::new(3, 0), GlyphPixel::Solid)
+            .unwrap();
+        font.glyphs[2]
+            .set_pixel(Coord::new(0, 0), GlyphPixel::Solid)
+            .unwrap();
+        font.glyphs[2]
+            .set_pixel(Coord::new(5, 4), GlyphPixel::Solid)
+            .unwrap();
+
+        assert_eq!(font.advance_width(0).unwrap(), 8);
+        assert_eq!(font.advance_width(1).unwrap(), 2);
+        assert_eq!(font.advance_width(2).unwrap(), 7);
+    }
+
+    #[test]
+    fn test_font_advance_width_invalid_index() {
+        let font = Font::default();
+
+        let result = font.advance_width(256);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidIndex { index: i, lenght: l } if i == 256 && l == 256
+        );
+    }
+
+    #[test]
+    fn test_font_changed_glyphs() {
+        let font = Font::default();
+        let mut other = Font::default();
+
+        assert!(font.changed_glyphs(&other).unwrap().is_empty());
+
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(0, 0), GlyphPixel::Solid)
+            .unwrap();
+        other.set_glyph(65, glyph).unwrap();
+
+        assert_eq!(font.changed_glyphs(&other).unwrap(), vec![65]);
+    }
+
+    #[test]
+    fn test_font_subset() {
+        let mut font = Font::default();
+        for index in [10, 65, 200] {
+            let mut glyph = Glyph::default();
+            glyph
+                .set_pixel(Coord::new(index % 8, 0), GlyphPixel::Solid)
+                .unwrap();
+            font.set_glyph(index, glyph).unwrap();
+        }
+
+        let result = font.subset(&[65, 10, 200, 65]);
+        assert!(result.is_ok());
+        let (subset, remap) = result.unwrap();
+
+        assert_eq!(remap.len(), 3);
+        assert_eq!(remap[&65], 0);
+        assert_eq!(remap[&10], 1);
+        assert_eq!(remap[&200], 2);
+        for (old, new) in remap.iter() {
+            assert_eq!(subset.glyphs[*new], font.glyphs[*old]);
+        }
+        assert!(subset
+            .iter()
+            .skip(3)
+            .all(|glyph| *glyph == Glyph::default()));
+    }
+
+    #[test]
+    fn test_font_subset_invalid_index() {
+        let font = Font::default();
+
+        let result = font.subset(&[1, 256]);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidIndex {
+                index: 256,
+                lenght: 256
+            }
+        );
+    }
+
     #[test]
     fn test_font_iter() {
         let font = Font::default();
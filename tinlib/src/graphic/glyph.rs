@@ -1,16 +1,34 @@
 //! Glyph implementation and manipulation.
-use std::fmt;
-use std::slice;
+use alloc::vec::Vec;
+use core::fmt;
 
 use crate::common::{
-    CommonError, Coord, CoordEnumerate, CoordEnumerateMut, CoordIter, Result, Size,
+    CommonError, Coord, CoordIter, Grid, GridEnumerate, GridEnumerateMut, GridIter, GridIterMut,
+    Result, Size,
 };
+#[cfg(feature = "image")]
+use crate::graphic::color::Color;
 
 /// The Glyph width.
 pub const GLYPH_WIDTH: usize = 8;
 /// The Glyph height.
 pub const GLYPH_HEIGHT: usize = 8;
 
+/// Number of bytes in a serialized Glyph, one per pixel.
+pub const GLYPH_BYTES: usize = GLYPH_WIDTH * GLYPH_HEIGHT;
+
+/// The RLE byte bit marking a `Solid` run.
+const RLE_SOLID_FLAG: u8 = 0b1000_0000;
+/// The RLE byte bits holding the run lenght.
+const RLE_LENGHT_MASK: u8 = 0b0111_1111;
+
+fn rle_byte(value: GlyphPixel, lenght: u8) -> u8 {
+    match value {
+        GlyphPixel::Empty => lenght,
+        GlyphPixel::Solid => RLE_SOLID_FLAG | lenght,
+    }
+}
+
 /// A Glyph pixel representation.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GlyphPixel {
@@ -21,104 +39,350 @@ pub enum GlyphPixel {
 }
 
 /// A iterator over all glyph pìxels.
-pub type GlyphPixelIter<'iter> = slice::Iter<'iter, GlyphPixel>;
+pub type GlyphPixelIter<'iter> = GridIter<'iter, GlyphPixel>;
 /// A mutable iterator over all glyph pìxels.
-pub type GlyphPixelIterMut<'iter> = slice::IterMut<'iter, GlyphPixel>;
+pub type GlyphPixelIterMut<'iter> = GridIterMut<'iter, GlyphPixel>;
 /// A enumeration iterator over all glyph pixels and their coords.
-pub type GlyphPixelEnumerate<'iter> = CoordEnumerate<'iter, GlyphPixel>;
+pub type GlyphPixelEnumerate<'iter> = GridEnumerate<'iter, GlyphPixel>;
 /// A mutable enumeration iterator over all glyph pixels and their coords.
-pub type GlyphPixelEnumerateMut<'iter> = CoordEnumerateMut<'iter, GlyphPixel>;
+pub type GlyphPixelEnumerateMut<'iter> = GridEnumerateMut<'iter, GlyphPixel>;
 
 /// A Glyph representation with 8x8 Pixels.
 #[derive(Clone, Copy)]
 pub struct Glyph {
-    data: [GlyphPixel; GLYPH_WIDTH * GLYPH_HEIGHT],
+    data: Grid<GlyphPixel, [GlyphPixel; GLYPH_BYTES]>,
 }
 
 impl Glyph {
+    /// Creates an empty Glyph with the given size.
+    ///
+    /// Glyphs are always 8x8, so any other size, including a zero width or
+    /// height, returns a `MismatchedSizes` error.
+    pub fn try_new(size: Size) -> Result<Glyph> {
+        let expected = Size::new(GLYPH_WIDTH, GLYPH_HEIGHT);
+        if size != expected {
+            return Err(CommonError::new_mismatched_sizes(expected, size));
+        }
+
+        Ok(Glyph::default())
+    }
+
+    /// Creates a Glyph from bytes.
+    ///
+    /// Each byte is a pixel in row-major order, where `0` is `Empty` and any
+    /// other value is `Solid`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Glyph> {
+        if bytes.len() != GLYPH_BYTES {
+            return Err(CommonError::new_invalid_lenght(bytes.len(), GLYPH_BYTES));
+        }
+
+        let mut glyph = Glyph::default();
+        for (pixel, byte) in glyph.data.iter_mut().zip(bytes.iter()) {
+            if *byte != 0 {
+                *pixel = GlyphPixel::Solid;
+            }
+        }
+
+        Ok(glyph)
+    }
+
+    /// Returns the Glyph as bytes.
+    ///
+    /// Each pixel is a byte in row-major order, `0` for `Empty` and `1` for
+    /// `Solid`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.data
+            .iter()
+            .map(|pixel| match pixel {
+                GlyphPixel::Empty => 0,
+                GlyphPixel::Solid => 1,
+            })
+            .collect()
+    }
+
+    /// Creates a Glyph with the given size from run-length encoded bytes.
+    ///
+    /// See `to_rle` for the encoding. The runs must cover exactly all the
+    /// glyph pixels, otherwise an `InvalidLenght` error is returned.
+    pub fn from_rle(size: Size, bytes: &[u8]) -> Result<Glyph> {
+        let mut glyph = Glyph::try_new(size)?;
+
+        let total: usize = bytes
+            .iter()
+            .map(|byte| (byte & RLE_LENGHT_MASK) as usize)
+            .sum();
+        if total != GLYPH_BYTES {
+            return Err(CommonError::new_invalid_lenght(total, GLYPH_BYTES));
+        }
+
+        let mut pixels = glyph.data.iter_mut();
+        for byte in bytes {
+            let value = if byte & RLE_SOLID_FLAG != 0 {
+                GlyphPixel::Solid
+            } else {
+                GlyphPixel::Empty
+            };
+
+            for pixel in pixels.by_ref().take((byte & RLE_LENGHT_MASK) as usize) {
+                *pixel = value;
+            }
+        }
+
+        Ok(glyph)
+    }
+
+    /// Returns the Glyph run-length encoded.
+    ///
+    /// Pixels are read in row-major order and each run of equal pixels is
+    /// a byte: the high bit is set for `Solid` runs and the low 7 bits hold
+    /// the run lenght. Runs longer than 127 pixels are split. Glyphs with
+    /// long runs encode to less than the 8 bytes of a bit-packed glyph.
+    pub fn to_rle(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        let mut run: Option<(GlyphPixel, u8)> = None;
+
+        for pixel in self.data.iter() {
+            run = match run {
+                Some((value, lenght)) if value == *pixel && lenght < RLE_LENGHT_MASK => {
+                    Some((value, lenght + 1))
+                }
+                Some((value, lenght)) => {
+                    bytes.push(rle_byte(value, lenght));
+                    Some((*pixel, 1))
+                }
+                None => Some((*pixel, 1)),
+            };
+        }
+
+        if let Some((value, lenght)) = run {
+            bytes.push(rle_byte(value, lenght));
+        }
+
+        bytes
+    }
+
     /// Returns the width.
     pub fn width(&self) -> usize {
-        GLYPH_WIDTH
+        self.data.width()
     }
 
     /// Returns the height.
     pub fn height(&self) -> usize {
-        GLYPH_HEIGHT
+        self.data.height()
     }
 
     /// Returns a Size.
     pub fn size(&self) -> Size {
-        Size::new(self.width(), self.height())
+        self.data.size()
     }
 
     /// Returns a pixel.
     pub fn get_pixel(&self, coord: Coord) -> Result<GlyphPixel> {
-        if !self.is_coord_valid(coord) {
-            return Err(CommonError::new_invalid_coord(coord, self.size()));
-        }
-
-        let index = self.get_index(coord);
-        Ok(self.data[index])
+        self.data.get(coord).copied()
     }
 
     /// Sets a pixel.
     pub fn set_pixel(&mut self, coord: Coord, value: GlyphPixel) -> Result<()> {
-        if !self.is_coord_valid(coord) {
-            return Err(CommonError::new_invalid_coord(coord, self.size()));
+        self.data.set(coord, value)
+    }
+
+    /// Sets all pixels of a row.
+    pub fn set_row(&mut self, y: usize, pixels: &[GlyphPixel]) -> Result<()> {
+        if y >= self.height() {
+            return Err(CommonError::new_invalid_index(y, self.height()));
+        }
+        if pixels.len() != self.width() {
+            return Err(CommonError::new_invalid_lenght(pixels.len(), self.width()));
+        }
+
+        for (x, pixel) in pixels.iter().enumerate() {
+            self.data.set(Coord::new(x, y), *pixel)?;
         }
 
-        let index = self.get_index(coord);
-        self.data[index] = value;
+        Ok(())
+    }
+
+    /// Sets all pixels of a column.
+    pub fn set_col(&mut self, x: usize, pixels: &[GlyphPixel]) -> Result<()> {
+        if x >= self.width() {
+            return Err(CommonError::new_invalid_index(x, self.width()));
+        }
+        if pixels.len() != self.height() {
+            return Err(CommonError::new_invalid_lenght(pixels.len(), self.height()));
+        }
+
+        for (y, pixel) in pixels.iter().enumerate() {
+            self.data.set(Coord::new(x, y), *pixel)?;
+        }
 
         Ok(())
     }
 
+    /// Returns a Glyph with pixels solid only where exactly one glyph is solid.
+    pub fn xor(&self, other: &Glyph) -> Result<Glyph> {
+        self.combine(other, |a, b| a != b)
+    }
+
+    /// Returns a Glyph with pixels solid only where both glyphs are solid.
+    pub fn and(&self, other: &Glyph) -> Result<Glyph> {
+        self.combine(other, |a, b| a && b)
+    }
+
+    /// Returns the number of solid pixels.
+    pub fn solid_count(&self) -> usize {
+        self.iter()
+            .filter(|pixel| **pixel == GlyphPixel::Solid)
+            .count()
+    }
+
+    /// Returns the ratio of solid pixels over all pixels, from `0.0` to `1.0`.
+    pub fn coverage(&self) -> f32 {
+        self.solid_count() as f32 / (self.width() * self.height()) as f32
+    }
+
+    /// Returns the origin and size of the smallest area holding all solid
+    /// pixels, or `None` if the Glyph is empty.
+    pub fn bounding_box(&self) -> Option<(Coord, Size)> {
+        let mut solid = self
+            .enumerate()
+            .filter(|(_, pixel)| **pixel == GlyphPixel::Solid)
+            .map(|(coord, _)| coord);
+
+        let first = solid.next()?;
+        let (min, max) = solid.fold((first, first), |(min, max), coord| {
+            (
+                Coord::new(min.x.min(coord.x), min.y.min(coord.y)),
+                Coord::new(max.x.max(coord.x), max.y.max(coord.y)),
+            )
+        });
+
+        Some((min, Size::new(max.x - min.x + 1, max.y - min.y + 1)))
+    }
+
+    /// Returns a Glyph with the solid pixels moved by `(dx, dy)`.
+    ///
+    /// Pixels moved outside the Glyph are dropped, and the vacated area is
+    /// left `Empty`.
+    pub fn shifted(&self, dx: isize, dy: isize) -> Glyph {
+        let mut glyph = Glyph::default();
+
+        for (coord, pixel) in self.enumerate() {
+            if *pixel != GlyphPixel::Solid {
+                continue;
+            }
+
+            if let Some(Ok(target)) = coord.offset(dx, dy).map(|c| glyph.data.get_mut(c)) {
+                *target = GlyphPixel::Solid;
+            }
+        }
+
+        glyph
+    }
+
+    /// Returns a Glyph with solid pixels on the outline ring, the empty pixels
+    /// next to a solid pixel, horizontally or vertically.
+    ///
+    /// The solid pixels of this Glyph are empty in the outline.
+    pub fn outline(&self) -> Glyph {
+        let is_solid = |coord: Option<Coord>| {
+            coord.and_then(|coord| self.get_pixel(coord).ok()) == Some(GlyphPixel::Solid)
+        };
+
+        let mut glyph = Glyph::default();
+        for (coord, pixel) in glyph.enumerate_mut() {
+            if is_solid(Some(coord)) {
+                continue;
+            }
+
+            if [(0, -1), (-1, 0), (1, 0), (0, 1)]
+                .iter()
+                .any(|(dx, dy)| is_solid(coord.offset(*dx, *dy)))
+            {
+                *pixel = GlyphPixel::Solid;
+            }
+        }
+
+        glyph
+    }
+
+    /// Returns the Glyph as an image, with solid and empty pixels as the given colors.
+    #[cfg(feature = "image")]
+    pub fn to_image(&self, solid: Color, empty: Color) -> image::RgbaImage {
+        let mut result = image::RgbaImage::new(self.width() as u32, self.height() as u32);
+
+        for (coord, pixel) in self.enumerate() {
+            let color = match pixel {
+                GlyphPixel::Empty => empty,
+                GlyphPixel::Solid => solid,
+            };
+            let rgba = image::Rgba([color.red(), color.green(), color.blue(), u8::MAX]);
+
+            result.put_pixel(coord.x as u32, coord.y as u32, rgba);
+        }
+
+        result
+    }
+
     /// Returns a iterator over the glyph's coords.
     pub fn coords(&self) -> CoordIter {
-        CoordIter::new(self.size())
+        self.data.coords()
     }
 
     /// Returns an iterator over all Glyph pixels.
-    pub fn iter(&self) -> GlyphPixelIter {
+    pub fn iter(&self) -> GlyphPixelIter<'_> {
         self.data.iter()
     }
 
     /// Returns a mutable iterator over all Glyph pixels.
-    pub fn iter_mut(&mut self) -> GlyphPixelIterMut {
+    pub fn iter_mut(&mut self) -> GlyphPixelIterMut<'_> {
         self.data.iter_mut()
     }
 
     /// Returns an enumerate iterator over glyph's coords and pixels.
-    pub fn enumerate(&self) -> GlyphPixelEnumerate {
-        GlyphPixelEnumerate::new(self.coords(), self.iter())
+    pub fn enumerate(&self) -> GlyphPixelEnumerate<'_> {
+        self.data.enumerate()
     }
 
     /// Returns a mutable enumerate iterator over glyph's coords and pixels.
-    pub fn enumerate_mut(&mut self) -> GlyphPixelEnumerateMut {
-        GlyphPixelEnumerateMut::new(self.coords(), self.iter_mut())
+    pub fn enumerate_mut(&mut self) -> GlyphPixelEnumerateMut<'_> {
+        self.data.enumerate_mut()
     }
 
-    fn is_coord_valid(&self, coord: Coord) -> bool {
-        coord.x < self.width() && coord.y < self.height()
-    }
+    fn combine<F>(&self, other: &Glyph, op: F) -> Result<Glyph>
+    where
+        F: Fn(bool, bool) -> bool,
+    {
+        if self.size() != other.size() {
+            return Err(CommonError::new_mismatched_sizes(self.size(), other.size()));
+        }
 
-    fn get_index(&self, coord: Coord) -> usize {
-        coord.x * self.width() + coord.y
+        let mut glyph = Glyph::default();
+        for ((pixel, a), b) in glyph.iter_mut().zip(self.iter()).zip(other.iter()) {
+            if op(*a == GlyphPixel::Solid, *b == GlyphPixel::Solid) {
+                *pixel = GlyphPixel::Solid;
+            }
+        }
+
+        Ok(glyph)
     }
 }
 
 impl Default for Glyph {
     /// Creates a Glyph with all pixels black.
     fn default() -> Self {
-        Self {
-            data: [GlyphPixel::Empty; GLYPH_WIDTH * GLYPH_HEIGHT],
-        }
+        let size = Size::new(GLYPH_WIDTH, GLYPH_HEIGHT);
+        let data = Grid::from_array(size, [GlyphPixel::Empty; GLYPH_BYTES])
+            .expect("glyph size area to be GLYPH_BYTES");
+
+        Self { data }
     }
 }
 
 impl PartialEq for Glyph {
+    /// Compares the size first and then all pixels, so glyphs with different
+    /// sizes are never equal.
     fn eq(&self, other: &Self) -> bool {
-        self.data.iter().zip(other.data.iter()).all(|(a, b)| a == b)
+        self.data == other.data
     }
 }
 
@@ -135,17 +399,131 @@ mod tests {
     use assert_matches::assert_matches;
 
     use super::*;
+    use crate::graphic::Font;
 
     #[test]
     fn test_glyph_default() {
         let glyph = Glyph::default();
 
-        assert_eq!(glyph.data.len(), GLYPH_WIDTH * GLYPH_HEIGHT);
+        assert_eq!(glyph.data.as_slice().len(), GLYPH_WIDTH * GLYPH_HEIGHT);
         for pixel in glyph.data.iter() {
             assert_eq!(*pixel, GlyphPixel::Empty);
         }
     }
 
+    #[test]
+    fn test_glyph_try_new() {
+        let result = Glyph::try_new(Size::new(GLYPH_WIDTH, GLYPH_HEIGHT));
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Glyph::default());
+    }
+
+    #[test]
+    fn test_glyph_try_new_zero_size() {
+        for size in [
+            Size::new(0, 0),
+            Size::new(0, 8),
+            Size::new(8, 0),
+            Size::new(usize::MAX, usize::MAX),
+        ] {
+            let result = Glyph::try_new(size);
+            assert!(result.is_err());
+            assert_matches!(
+                result.unwrap_err(),
+                CommonError::MismatchedSizes { expected: e, found: f } if e == Size::new(8, 8) && f == size
+            );
+        }
+    }
+
+    #[test]
+    fn test_glyph_from_bytes() {
+        let mut bytes = vec![0u8; GLYPH_BYTES];
+        bytes[GLYPH_WIDTH + 2] = 1;
+        bytes[GLYPH_BYTES - 1] = 255;
+
+        let result = Glyph::from_bytes(&bytes);
+        assert!(result.is_ok());
+
+        let glyph = result.unwrap();
+        assert_eq!(
+            glyph.get_pixel(Coord::new(2, 1)).unwrap(),
+            GlyphPixel::Solid
+        );
+        assert_eq!(
+            glyph.get_pixel(Coord::new(7, 7)).unwrap(),
+            GlyphPixel::Solid
+        );
+        assert_eq!(glyph.iter().filter(|p| **p == GlyphPixel::Solid).count(), 2);
+    }
+
+    #[test]
+    fn test_glyph_from_bytes_invalid_lenght() {
+        let bytes = vec![0u8; GLYPH_BYTES - 1];
+
+        let result = Glyph::from_bytes(&bytes);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidLenght { lenght: l, expected: e } if l == GLYPH_BYTES - 1 && e == GLYPH_BYTES
+        );
+    }
+
+    #[test]
+    fn test_glyph_to_bytes() {
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(2, 1), GlyphPixel::Solid)
+            .unwrap();
+
+        let bytes = glyph.to_bytes();
+        assert_eq!(bytes.len(), GLYPH_BYTES);
+        assert_eq!(bytes[GLYPH_WIDTH + 2], 1);
+        assert_eq!(bytes.iter().filter(|b| **b != 0).count(), 1);
+        assert_eq!(Glyph::from_bytes(&bytes).unwrap(), glyph);
+    }
+
+    #[test]
+    fn test_glyph_rle_round_trip() {
+        let mut glyph = Glyph::default();
+        glyph.set_row(3, &[GlyphPixel::Solid; GLYPH_WIDTH]).unwrap();
+        glyph.set_row(4, &[GlyphPixel::Solid; GLYPH_WIDTH]).unwrap();
+
+        let rle = glyph.to_rle();
+        assert_eq!(rle, vec![24, 0b1000_0000 | 16, 24]);
+        assert!(rle.len() < GLYPH_BYTES / 8);
+        assert_eq!(Glyph::from_rle(glyph.size(), &rle).unwrap(), glyph);
+    }
+
+    #[test]
+    fn test_glyph_rle_long_run() {
+        let mut glyph = Glyph::default();
+        glyph.iter_mut().for_each(|p| *p = GlyphPixel::Solid);
+        glyph
+            .set_pixel(Coord::new(7, 7), GlyphPixel::Empty)
+            .unwrap();
+
+        let rle = glyph.to_rle();
+        assert_eq!(rle, vec![0b1000_0000 | 63, 1]);
+        assert_eq!(Glyph::from_rle(glyph.size(), &rle).unwrap(), glyph);
+    }
+
+    #[test]
+    fn test_glyph_from_rle_invalid_lenght() {
+        let result = Glyph::from_rle(Size::new(8, 8), &[60, 0b1000_0000 | 5]);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidLenght { lenght: l, expected: e } if l == 65 && e == GLYPH_BYTES
+        );
+    }
+
+    #[test]
+    fn test_glyph_from_rle_invalid_size() {
+        let result = Glyph::from_rle(Size::new(4, 4), &[16]);
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CommonError::MismatchedSizes { .. });
+    }
+
     #[test]
     fn test_glyph_width_height_and_size() {
         let glyph = Glyph::default();
@@ -204,6 +582,268 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_glyph_set_row() {
+        let mut glyph = Glyph::default();
+        let mut pixels = [GlyphPixel::Empty; GLYPH_WIDTH];
+        pixels[0] = GlyphPixel::Solid;
+        pixels[3] = GlyphPixel::Solid;
+
+        let result = glyph.set_row(0, &pixels);
+        assert!(result.is_ok());
+
+        for (x, pixel) in pixels.iter().enumerate() {
+            assert_eq!(glyph.get_pixel(Coord::new(x, 0)).unwrap(), *pixel);
+        }
+        assert_eq!(glyph.iter().filter(|p| **p == GlyphPixel::Solid).count(), 2);
+    }
+
+    #[test]
+    fn test_glyph_set_row_invalid_index() {
+        let mut glyph = Glyph::default();
+        let pixels = [GlyphPixel::Solid; GLYPH_WIDTH];
+
+        let result = glyph.set_row(GLYPH_HEIGHT, &pixels);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidIndex { index: i, lenght: l } if i == GLYPH_HEIGHT && l == GLYPH_HEIGHT
+        );
+    }
+
+    #[test]
+    fn test_glyph_set_row_invalid_lenght() {
+        let mut glyph = Glyph::default();
+        let pixels = [GlyphPixel::Solid; 4];
+
+        let result = glyph.set_row(0, &pixels);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidLenght { lenght: l, expected: e } if l == 4 && e == GLYPH_WIDTH
+        );
+        assert_eq!(glyph, Glyph::default());
+    }
+
+    #[test]
+    fn test_glyph_set_col() {
+        let mut glyph = Glyph::default();
+        let pixels = [GlyphPixel::Solid; GLYPH_HEIGHT];
+
+        let result = glyph.set_col(2, &pixels);
+        assert!(result.is_ok());
+
+        for y in 0..GLYPH_HEIGHT {
+            assert_eq!(
+                glyph.get_pixel(Coord::new(2, y)).unwrap(),
+                GlyphPixel::Solid
+            );
+        }
+        assert_eq!(
+            glyph.iter().filter(|p| **p == GlyphPixel::Solid).count(),
+            GLYPH_HEIGHT
+        );
+    }
+
+    #[test]
+    fn test_glyph_set_col_invalid_lenght() {
+        let mut glyph = Glyph::default();
+        let pixels = [GlyphPixel::Solid; GLYPH_HEIGHT + 1];
+
+        let result = glyph.set_col(0, &pixels);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidLenght { lenght: l, expected: e } if l == GLYPH_HEIGHT + 1 && e == GLYPH_HEIGHT
+        );
+    }
+
+    #[test]
+    fn test_glyph_solid_count_and_coverage() {
+        let mut glyph = Glyph::default();
+        assert_eq!(glyph.solid_count(), 0);
+        assert_eq!(glyph.coverage(), 0.0);
+
+        for y in 0..GLYPH_HEIGHT / 2 {
+            glyph.set_row(y, &[GlyphPixel::Solid; GLYPH_WIDTH]).unwrap();
+        }
+        assert_eq!(glyph.solid_count(), GLYPH_BYTES / 2);
+        assert_eq!(glyph.coverage(), 0.5);
+    }
+
+    #[test]
+    fn test_glyph_bounding_box() {
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(2, 1), GlyphPixel::Solid)
+            .unwrap();
+        glyph
+            .set_pixel(Coord::new(4, 6), GlyphPixel::Solid)
+            .unwrap();
+        glyph
+            .set_pixel(Coord::new(3, 0), GlyphPixel::Solid)
+            .unwrap();
+
+        assert_eq!(
+            glyph.bounding_box(),
+            Some((Coord::new(2, 0), Size::new(3, 7)))
+        );
+    }
+
+    #[test]
+    fn test_glyph_bounding_box_empty() {
+        let glyph = Glyph::default();
+
+        assert_eq!(glyph.bounding_box(), None);
+    }
+
+    #[test]
+    fn test_glyph_shifted() {
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(3, 2), GlyphPixel::Solid)
+            .unwrap();
+
+        let mut expected = Glyph::default();
+        expected
+            .set_pixel(Coord::new(4, 2), GlyphPixel::Solid)
+            .unwrap();
+
+        assert_eq!(glyph.shifted(1, 0), expected);
+        assert_eq!(glyph.shifted(0, 0), glyph);
+    }
+
+    #[test]
+    fn test_glyph_shifted_off_the_edge() {
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(7, 0), GlyphPixel::Solid)
+            .unwrap();
+
+        assert_eq!(glyph.shifted(1, 0), Glyph::default());
+        assert_eq!(glyph.shifted(0, -1), Glyph::default());
+    }
+
+    #[test]
+    fn test_glyph_outline() {
+        let mut glyph = Glyph::default();
+        for (x, y) in [(2, 2), (3, 2), (2, 3), (3, 3)] {
+            glyph
+                .set_pixel(Coord::new(x, y), GlyphPixel::Solid)
+                .unwrap();
+        }
+
+        let mut expected = Glyph::default();
+        for (x, y) in [
+            (2, 1),
+            (3, 1),
+            (1, 2),
+            (4, 2),
+            (1, 3),
+            (4, 3),
+            (2, 4),
+            (3, 4),
+        ] {
+            expected
+                .set_pixel(Coord::new(x, y), GlyphPixel::Solid)
+                .unwrap();
+        }
+
+        let outline = glyph.outline();
+        assert_eq!(outline, expected);
+        assert_eq!(
+            outline.get_pixel(Coord::new(2, 2)).unwrap(),
+            GlyphPixel::Empty
+        );
+        assert_eq!(
+            outline.get_pixel(Coord::new(1, 1)).unwrap(),
+            GlyphPixel::Empty
+        );
+    }
+
+    #[test]
+    fn test_glyph_outline_at_the_edge() {
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(0, 0), GlyphPixel::Solid)
+            .unwrap();
+
+        let mut expected = Glyph::default();
+        expected
+            .set_pixel(Coord::new(1, 0), GlyphPixel::Solid)
+            .unwrap();
+        expected
+            .set_pixel(Coord::new(0, 1), GlyphPixel::Solid)
+            .unwrap();
+
+        assert_eq!(glyph.outline(), expected);
+    }
+
+    #[test]
+    fn test_glyph_xor() {
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(1, 1), GlyphPixel::Solid)
+            .unwrap();
+        glyph
+            .set_pixel(Coord::new(2, 3), GlyphPixel::Solid)
+            .unwrap();
+
+        let result = glyph.xor(&glyph);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Glyph::default());
+
+        let result = glyph.xor(&Glyph::default());
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), glyph);
+    }
+
+    #[test]
+    fn test_glyph_and() {
+        let mut glyph_1 = Glyph::default();
+        glyph_1
+            .set_pixel(Coord::new(0, 0), GlyphPixel::Solid)
+            .unwrap();
+        glyph_1
+            .set_pixel(Coord::new(1, 0), GlyphPixel::Solid)
+            .unwrap();
+
+        let mut glyph_2 = Glyph::default();
+        glyph_2
+            .set_pixel(Coord::new(1, 0), GlyphPixel::Solid)
+            .unwrap();
+        glyph_2
+            .set_pixel(Coord::new(2, 0), GlyphPixel::Solid)
+            .unwrap();
+
+        let mut expected = Glyph::default();
+        expected
+            .set_pixel(Coord::new(1, 0), GlyphPixel::Solid)
+            .unwrap();
+
+        let result = glyph_1.and(&glyph_2);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), expected);
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_glyph_to_image() {
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(3, 1), GlyphPixel::Solid)
+            .unwrap();
+        let solid = Color::new(255, 128, 0);
+        let empty = Color::new(0, 0, 0);
+
+        let image = glyph.to_image(solid, empty);
+
+        assert_eq!(image.width() as usize, glyph.width());
+        assert_eq!(image.height() as usize, glyph.height());
+        assert_eq!(image.get_pixel(3, 1), &image::Rgba([255, 128, 0, 255]));
+        assert_eq!(image.get_pixel(1, 3), &image::Rgba([0, 0, 0, 255]));
+    }
+
     #[test]
     fn test_glyph_coords() {
         let glyph = Glyph::default();
@@ -214,10 +854,10 @@ mod tests {
             assert_eq!(coord.x, x);
             assert_eq!(coord.y, y);
 
-            y += 1;
-            if y == glyph.width() {
-                y = 0;
-                x += 1;
+            x += 1;
+            if x == glyph.width() {
+                x = 0;
+                y += 1;
             }
         }
     }
@@ -284,10 +924,44 @@ mod tests {
 
         assert_eq!(glyph_1, glyph_2);
 
-        glyph_2.data[0] = GlyphPixel::Solid;
+        glyph_2.data.as_mut_slice()[0] = GlyphPixel::Solid;
         assert_ne!(glyph_1, glyph_2);
     }
 
+    #[test]
+    fn test_glyph_partialeq_size_in_font_changed_glyphs() {
+        let tall = Glyph {
+            data: Grid::from_array(Size::new(4, 16), [GlyphPixel::Empty; GLYPH_BYTES]).unwrap(),
+        };
+        let mut font = Font::default();
+        font.set_glyph(3, tall).unwrap();
+
+        let result = font.changed_glyphs(&Font::default());
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::MismatchedSizes { expected: e, found: f }
+                if e == Size::new(4, 16) && f == Size::new(GLYPH_WIDTH, GLYPH_HEIGHT)
+        );
+    }
+
+    #[test]
+    fn test_glyph_partialeq_size() {
+        let pixels = [GlyphPixel::Empty; GLYPH_BYTES];
+        let wide = Glyph {
+            data: Grid::from_array(Size::new(16, 4), pixels).unwrap(),
+        };
+        let tall_1 = Glyph {
+            data: Grid::from_array(Size::new(4, 16), pixels).unwrap(),
+        };
+        let tall_2 = tall_1;
+
+        assert_ne!(wide, tall_1);
+        assert_ne!(tall_1, wide);
+        assert_ne!(tall_1, Glyph::default());
+        assert_eq!(tall_1, tall_2);
+    }
+
     #[test]
     fn test_glyph_debug() {
         let glyph = Glyph::default();
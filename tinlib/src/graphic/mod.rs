@@ -1,13 +1,20 @@
 //! Graphic utilities.
+mod animator;
 mod color;
 mod font;
 mod glyph;
 mod palette;
 
+pub use crate::graphic::animator::PaletteAnimator;
 pub use crate::graphic::color::Color;
 pub use crate::graphic::font::{Font, FontGlyphIter, FontGlyphIterMut};
 pub use crate::graphic::glyph::{
     Glyph, GlyphPixel, GlyphPixelEnumerate, GlyphPixelEnumerateMut, GlyphPixelIter,
     GlyphPixelIterMut,
 };
-pub use crate::graphic::palette::{Palette, PaletteColorIter, PaletteColorIterMut};
+pub use crate::graphic::palette::{Palette, PaletteColorIter, PaletteColorIterMut, PaletteIndex};
+
+#[cfg(feature = "std")]
+pub(crate) use crate::graphic::glyph::{GLYPH_BYTES, GLYPH_WIDTH};
+#[cfg(feature = "std")]
+pub(crate) use crate::graphic::palette::CARTRIDGE_BYTES_PER_COLOR;
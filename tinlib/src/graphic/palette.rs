@@ -1,12 +1,25 @@
 //! Palette implementation and manipulation.
-use std::fmt;
-use std::slice;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::result::Result as StdResult;
+use core::slice;
 
 use crate::common::{CommonError, Result};
 use crate::graphic::color::Color;
 
 /// Number of colors in a Palette.
 const COLORS_IN_PALETTE: usize = 16;
+/// Number of bytes of a color in a cartridge palette.
+pub(crate) const CARTRIDGE_BYTES_PER_COLOR: usize = 3;
+/// Valid byte lenghts of a cartridge palette, for 0, 4, 8 or 16 colors.
+const CARTRIDGE_PALETTE_SIZES: [usize; 4] = [0, 12, 24, 48];
+
+/// The 16 colors of the standard CGA palette, in their usual order.
+const STANDARD_COLORS: [u32; COLORS_IN_PALETTE] = [
+    0x000000, 0x0000AA, 0x00AA00, 0x00AAAA, 0xAA0000, 0xAA00AA, 0xAA5500, 0xAAAAAA, 0x555555,
+    0x5555FF, 0x55FF55, 0x55FFFF, 0xFF5555, 0xFF55FF, 0xFFFF55, 0xFFFFFF,
+];
 
 /// A iterator over all palette colors.
 pub type PaletteColorIter<'iter> = slice::Iter<'iter, Color>;
@@ -21,6 +34,86 @@ pub struct Palette {
 }
 
 impl Palette {
+    /// Creates a Palette with the 16 colors of the standard CGA palette.
+    ///
+    /// Unlike the all black default, it's a usable starting palette.
+    pub fn standard() -> Self {
+        let mut palette = Self::default();
+
+        for (color, hex) in palette.colors.iter_mut().zip(STANDARD_COLORS.iter()) {
+            *color = Color::new_from_hex(*hex);
+        }
+
+        palette
+    }
+
+    /// Creates a Palette with a linear gradient from `start` to `end`.
+    ///
+    /// The gradient uses `steps` colors, including both endpoints, clamped
+    /// between 2 and the palette lenght. Colors after the gradient are black.
+    pub fn gradient(start: Color, end: Color, steps: usize) -> Self {
+        let steps = steps.clamp(2, COLORS_IN_PALETTE);
+        let mut palette = Self::default();
+
+        for (index, color) in palette.colors.iter_mut().take(steps).enumerate() {
+            let t = index as f32 / (steps - 1) as f32;
+            *color = start.lerp(end, t);
+        }
+
+        palette
+    }
+
+    /// Creates a Palette from the data of a cartridge palette chunk.
+    ///
+    /// The data is a list of raw RGB colors, 3 bytes each in red, green, blue
+    /// order, so the valid 0, 12, 24 and 48 bytes sizes hold 0, 4, 8 and 16
+    /// colors. The colors fill the palette from index 0 and the remaining
+    /// colors are black. Any other size returns an `InvalidLenght` error.
+    pub fn from_cartridge_bytes(bytes: &[u8]) -> Result<Self> {
+        if !CARTRIDGE_PALETTE_SIZES.contains(&bytes.len()) {
+            return Err(CommonError::new_invalid_lenght(
+                bytes.len(),
+                COLORS_IN_PALETTE * CARTRIDGE_BYTES_PER_COLOR,
+            ));
+        }
+
+        let mut palette = Self::default();
+        for (color, rgb) in palette
+            .colors
+            .iter_mut()
+            .zip(bytes.chunks_exact(CARTRIDGE_BYTES_PER_COLOR))
+        {
+            *color = Color::new(rgb[0], rgb[1], rgb[2]);
+        }
+
+        Ok(palette)
+    }
+
+    /// Returns a Palette with every color channel scaled by `factor`,
+    /// rounded and saturating at 255.
+    ///
+    /// A factor of `1.0` returns the same colors, and negative factors
+    /// return black.
+    pub fn with_brightness(&self, factor: f32) -> Palette {
+        let channel = |value: u8| (value as f32 * factor + 0.5).clamp(0.0, 255.0) as u8;
+
+        let mut palette = *self;
+        for color in palette.colors.iter_mut() {
+            *color = Color::new(
+                channel(color.red()),
+                channel(color.green()),
+                channel(color.blue()),
+            );
+        }
+
+        palette
+    }
+
+    /// Returns the Palette as bytes, in red, green, blue order per color.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        Color::slice_as_bytes(&self.colors).to_vec()
+    }
+
     /// Returns the lenght.
     pub fn lenght(&self) -> usize {
         COLORS_IN_PALETTE
@@ -46,19 +139,157 @@ impl Palette {
         Ok(())
     }
 
+    /// Returns the index of the first color equal to `color`.
+    pub fn index_of(&self, color: Color) -> Option<usize> {
+        self.colors.iter().position(|c| *c == color)
+    }
+
+    /// Returns the index of the color closest to `color`, by squared RGB
+    /// distance. Ties resolve to the lowest index.
+    pub fn nearest(&self, color: Color) -> usize {
+        let distance = |other: &Color| {
+            let channel = |a: u8, b: u8| {
+                let delta = a as i32 - b as i32;
+                (delta * delta) as u32
+            };
+
+            channel(color.red(), other.red())
+                + channel(color.green(), other.green())
+                + channel(color.blue(), other.blue())
+        };
+
+        self.colors
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, other)| distance(other))
+            .map(|(index, _)| index)
+            .unwrap_or_default()
+    }
+
+    /// Returns a Palette with the distinct colors of this Palette, in order,
+    /// followed by the colors of `other` it doesn't have yet.
+    ///
+    /// Colors after the merged ones are black. Duplicated colors of this
+    /// Palette are dropped too, so its colors can move to lower indices. Also
+    /// returns, for each index of this Palette and then of `other`, the index
+    /// of its color in the merged Palette. Returns an `InvalidLenght` error if
+    /// the distinct colors don't fit in a Palette.
+    pub fn merge(&self, other: &Palette) -> Result<(Palette, Vec<usize>, Vec<usize>)> {
+        let mut merged = Palette::default();
+        let mut lenght = 0;
+
+        let mut add = |color: Color| {
+            if let Some(index) = merged.colors[..lenght].iter().position(|c| *c == color) {
+                return Ok(index);
+            }
+
+            if lenght == COLORS_IN_PALETTE {
+                return Err(CommonError::new_invalid_lenght(
+                    lenght + 1,
+                    COLORS_IN_PALETTE,
+                ));
+            }
+
+            merged.colors[lenght] = color;
+            lenght += 1;
+
+            Ok(lenght - 1)
+        };
+
+        let mut self_remap = Vec::with_capacity(COLORS_IN_PALETTE);
+        for color in self.iter() {
+            self_remap.push(add(*color)?);
+        }
+
+        let mut other_remap = Vec::with_capacity(COLORS_IN_PALETTE);
+        for color in other.iter() {
+            other_remap.push(add(*color)?);
+        }
+
+        Ok((merged, self_remap, other_remap))
+    }
+
+    /// Sorts the colors from darkest to lightest, using the Rec. 601 luma.
+    ///
+    /// Sorting moves colors to new indices, so anything referencing colors by
+    /// index must be updated. The returned array maps each old index to its
+    /// new index.
+    pub fn sort_by_luminance(&mut self) -> [usize; COLORS_IN_PALETTE] {
+        self.sort_by_color_key(|color| {
+            299 * color.red() as u32 + 587 * color.green() as u32 + 114 * color.blue() as u32
+        })
+    }
+
+    /// Sorts the colors by hue, from red through green and blue, with grays first.
+    ///
+    /// Like `sort_by_luminance`, returns an array mapping each old index to its
+    /// new index.
+    pub fn sort_by_hue(&mut self) -> [usize; COLORS_IN_PALETTE] {
+        self.sort_by_color_key(hue_key)
+    }
+
     /// Returns an iterator over all palette pixels.
-    pub fn iter(&self) -> PaletteColorIter {
+    pub fn iter(&self) -> PaletteColorIter<'_> {
         self.colors.iter()
     }
 
     /// Returns a mutable iterator over all palette pixels.
-    pub fn iter_mut(&mut self) -> PaletteColorIterMut {
+    pub fn iter_mut(&mut self) -> PaletteColorIterMut<'_> {
         self.colors.iter_mut()
     }
 
     fn is_index_valid(&self, index: usize) -> bool {
         index < self.lenght()
     }
+
+    fn sort_by_color_key<K, F>(&mut self, key: F) -> [usize; COLORS_IN_PALETTE]
+    where
+        K: Ord,
+        F: Fn(Color) -> K,
+    {
+        let mut order = [0; COLORS_IN_PALETTE];
+        for (index, value) in order.iter_mut().enumerate() {
+            *value = index;
+        }
+        order.sort_by_key(|index| key(self.colors[*index]));
+
+        let old_colors = self.colors;
+        let mut mapping = [0; COLORS_IN_PALETTE];
+        for (new_index, old_index) in order.iter().enumerate() {
+            self.colors[new_index] = old_colors[*old_index];
+            mapping[*old_index] = new_index;
+        }
+
+        mapping
+    }
+}
+
+/// Returns the hue of a color in hundredths of a degree, or `None` for grays,
+/// so they sort before every hue.
+fn hue_key(color: Color) -> Option<u32> {
+    let (r, g, b) = (
+        color.red() as f32,
+        color.green() as f32,
+        color.blue() as f32,
+    );
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    if delta == 0.0 {
+        return None;
+    }
+
+    let hue = if max == r {
+        60.0 * ((g - b) / delta)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let hue = if hue < 0.0 { hue + 360.0 } else { hue };
+
+    Some((hue * 100.0) as u32)
 }
 
 impl Default for Palette {
@@ -70,6 +301,34 @@ impl Default for Palette {
     }
 }
 
+/// A validated index of a Palette color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PaletteIndex(u8);
+
+impl PaletteIndex {
+    /// Returns the index value.
+    pub fn value(self) -> usize {
+        self.0 as usize
+    }
+
+    /// Returns the color at this index in a Palette.
+    pub fn get(self, palette: &Palette) -> Result<Color> {
+        palette.get_color(self.value())
+    }
+}
+
+impl TryFrom<usize> for PaletteIndex {
+    type Error = CommonError;
+
+    fn try_from(value: usize) -> StdResult<Self, Self::Error> {
+        if value >= COLORS_IN_PALETTE {
+            return Err(CommonError::new_invalid_index(value, COLORS_IN_PALETTE));
+        }
+
+        Ok(Self(value as u8))
+    }
+}
+
 impl fmt::Debug for Palette {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let data: Vec<&Color> = self.colors.iter().collect();
@@ -90,6 +349,33 @@ mod tests {
         assert_eq!(palette.colors.len(), COLORS_IN_PALETTE);
     }
 
+    #[test]
+    fn test_palette_gradient() {
+        let black = Color::new(0, 0, 0);
+        let white = Color::new(255, 255, 255);
+
+        let palette = Palette::gradient(black, white, 5);
+
+        assert_eq!(palette.colors[0], black);
+        assert_eq!(palette.colors[2], Color::new(128, 128, 128));
+        assert_eq!(palette.colors[4], white);
+        assert!(palette.colors[5..].iter().all(|c| *c == Color::default()));
+    }
+
+    #[test]
+    fn test_palette_gradient_clamped_steps() {
+        let black = Color::new(0, 0, 0);
+        let white = Color::new(255, 255, 255);
+
+        let palette = Palette::gradient(black, white, 0);
+        assert_eq!(palette.colors[0], black);
+        assert_eq!(palette.colors[1], white);
+
+        let palette = Palette::gradient(black, white, 100);
+        assert_eq!(palette.colors[0], black);
+        assert_eq!(palette.colors[COLORS_IN_PALETTE - 1], white);
+    }
+
     #[test]
     fn test_palette_len() {
         let palette = Palette::default();
@@ -145,6 +431,234 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_palette_standard() {
+        let palette = Palette::standard();
+
+        assert_eq!(palette.iter().count(), 16);
+        for (index, color) in palette.iter().enumerate() {
+            assert_eq!(palette.index_of(*color), Some(index));
+        }
+        assert_eq!(palette.get_color(0).unwrap(), Color::new(0, 0, 0));
+        assert_eq!(palette.get_color(6).unwrap(), Color::new(170, 85, 0));
+        assert_eq!(palette.get_color(15).unwrap(), Color::new(255, 255, 255));
+    }
+
+    #[test]
+    fn test_palette_to_bytes() {
+        let mut palette = Palette::default();
+        palette.set_color(1, Color::new(1, 2, 3)).unwrap();
+
+        let bytes = palette.to_bytes();
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(&bytes[..6], &[0, 0, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_palette_from_cartridge_bytes() {
+        for (size, colors) in [(0, 0), (12, 4), (24, 8), (48, 16)] {
+            let bytes: Vec<u8> = (1..=size as u8).collect();
+            let palette = Palette::from_cartridge_bytes(&bytes).unwrap();
+
+            for index in 0..colors {
+                let value = index as u8 * 3;
+                assert_eq!(
+                    palette.get_color(index).unwrap(),
+                    Color::new(value + 1, value + 2, value + 3)
+                );
+            }
+            for index in colors..palette.lenght() {
+                assert_eq!(palette.get_color(index).unwrap(), Color::default());
+            }
+        }
+    }
+
+    #[test]
+    fn test_palette_from_cartridge_bytes_round_trip() {
+        let palette = Palette::standard();
+
+        let result = Palette::from_cartridge_bytes(&palette.to_bytes()).unwrap();
+        assert_eq!(result.colors, palette.colors);
+    }
+
+    #[test]
+    fn test_palette_from_cartridge_bytes_invalid_lenght() {
+        for size in [3, 4, 8, 16, 51] {
+            let result = Palette::from_cartridge_bytes(&vec![0; size]);
+            assert!(result.is_err());
+            assert_matches!(
+                result.unwrap_err(),
+                CommonError::InvalidLenght { lenght: l, expected: e } if l == size && e == 48
+            );
+        }
+    }
+
+    #[test]
+    fn test_palette_with_brightness() {
+        let mut palette = Palette::default();
+        palette.set_color(0, Color::new(200, 100, 51)).unwrap();
+        palette.set_color(1, Color::new(200, 128, 10)).unwrap();
+
+        let dimmed = palette.with_brightness(0.5);
+        assert_eq!(dimmed.colors[0], Color::new(100, 50, 26));
+
+        let brightened = palette.with_brightness(2.0);
+        assert_eq!(brightened.colors[1], Color::new(255, 255, 20));
+
+        let same = palette.with_brightness(1.0);
+        assert_eq!(same.colors, palette.colors);
+
+        let black = palette.with_brightness(-1.0);
+        assert!(black.iter().all(|color| *color == Color::default()));
+    }
+
+    #[test]
+    fn test_palette_index_of() {
+        let mut palette = Palette::default();
+        let color = Color::new(255, 255, 255);
+        palette.set_color(3, color).unwrap();
+        palette.set_color(7, color).unwrap();
+
+        assert_eq!(palette.index_of(color), Some(3));
+        assert_eq!(palette.index_of(Color::default()), Some(0));
+        assert_eq!(palette.index_of(Color::new(1, 2, 3)), None);
+    }
+
+    #[test]
+    fn test_palette_merge() {
+        let black = Color::new(0, 0, 0);
+        let red = Color::new(255, 0, 0);
+        let blue = Color::new(0, 0, 255);
+
+        let mut palette = Palette::default();
+        palette.set_color(1, red).unwrap();
+        let mut other = Palette::default();
+        other.set_color(1, blue).unwrap();
+
+        let result = palette.merge(&other);
+        assert!(result.is_ok());
+        let (merged, self_remap, other_remap) = result.unwrap();
+
+        assert_eq!(&merged.colors[..3], &[black, red, blue]);
+        assert!(merged.iter().skip(3).all(|color| *color == black));
+
+        let mut expected = vec![0; 16];
+        expected[1] = 1;
+        assert_eq!(self_remap, expected);
+
+        let mut expected = vec![0; 16];
+        expected[1] = 2;
+        assert_eq!(other_remap, expected);
+    }
+
+    #[test]
+    fn test_palette_merge_remaps_self() {
+        let black = Color::new(0, 0, 0);
+        let red = Color::new(255, 0, 0);
+
+        let mut palette = Palette::default();
+        palette.set_color(7, red).unwrap();
+        let other = Palette::default();
+
+        let result = palette.merge(&other);
+        assert!(result.is_ok());
+        let (merged, self_remap, _) = result.unwrap();
+
+        assert_eq!(&merged.colors[..2], &[black, red]);
+        for (index, new_index) in self_remap.iter().enumerate() {
+            assert_eq!(merged.colors[*new_index], palette.colors[index]);
+        }
+        assert_eq!(self_remap[7], 1);
+    }
+
+    #[test]
+    fn test_palette_merge_too_many_colors() {
+        let palette = Palette::standard();
+        let mut other = Palette::default();
+        other.set_color(0, Color::new(1, 2, 3)).unwrap();
+
+        let result = palette.merge(&other);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidLenght {
+                lenght: 17,
+                expected: 16
+            }
+        );
+    }
+
+    #[test]
+    fn test_palette_nearest() {
+        let mut palette = Palette::default();
+        palette.set_color(1, Color::new(255, 255, 255)).unwrap();
+        palette.set_color(2, Color::new(255, 0, 0)).unwrap();
+
+        assert_eq!(palette.nearest(Color::new(10, 20, 10)), 0);
+        assert_eq!(palette.nearest(Color::new(200, 220, 240)), 1);
+        assert_eq!(palette.nearest(Color::new(200, 30, 10)), 2);
+        assert_eq!(palette.nearest(Color::new(255, 255, 255)), 1);
+    }
+
+    #[test]
+    fn test_palette_sort_by_luminance() {
+        let mut palette = Palette::default();
+        let white = Color::new(255, 255, 255);
+        let gray = Color::new(128, 128, 128);
+        let dark = Color::new(30, 30, 30);
+        let light = Color::new(200, 200, 200);
+        palette.colors[..4].copy_from_slice(&[white, gray, dark, light]);
+        for color in palette.colors[4..].iter_mut() {
+            *color = white;
+        }
+
+        let mapping = palette.sort_by_luminance();
+
+        assert_eq!(&palette.colors[..4], &[dark, gray, light, white]);
+        assert!(palette
+            .colors
+            .windows(2)
+            .all(|pair| pair[0].to_grayscale().red() <= pair[1].to_grayscale().red()));
+        assert_eq!(&mapping[..4], &[3, 1, 0, 2]);
+        assert_eq!(mapping[4], 4);
+    }
+
+    #[test]
+    fn test_palette_sort_by_hue() {
+        let mut palette = Palette::default();
+        let red = Color::new(255, 0, 0);
+        let green = Color::new(0, 255, 0);
+        let blue = Color::new(0, 0, 255);
+        let yellow = Color::new(255, 255, 0);
+        palette.colors[..4].copy_from_slice(&[blue, yellow, green, red]);
+        for color in palette.colors[4..].iter_mut() {
+            *color = blue;
+        }
+
+        let mapping = palette.sort_by_hue();
+
+        assert_eq!(&palette.colors[..4], &[red, yellow, green, blue]);
+        assert_eq!(palette.colors[mapping[1]], yellow);
+        assert_eq!(palette.colors[mapping[2]], green);
+    }
+
+    #[test]
+    fn test_palette_sort_by_hue_grays_first() {
+        let mut palette = Palette::default();
+        let red = Color::new(255, 0, 0);
+        let gray = Color::new(128, 128, 128);
+        for color in palette.colors.iter_mut() {
+            *color = red;
+        }
+        palette.colors[15] = gray;
+
+        let mapping = palette.sort_by_hue();
+
+        assert_eq!(palette.colors[0], gray);
+        assert_eq!(mapping[15], 0);
+        assert!(palette.colors[1..].iter().all(|color| *color == red));
+    }
+
     #[test]
     fn test_palette_iter() {
         let palette = Palette::default();
@@ -169,6 +683,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_paletteindex_tryfrom() {
+        let mut palette = Palette::default();
+        let color = Color::new(255, 255, 255);
+        palette.set_color(15, color).unwrap();
+
+        let result = PaletteIndex::try_from(15usize);
+        assert!(result.is_ok());
+
+        let index = result.unwrap();
+        assert_eq!(index.value(), 15);
+        assert_eq!(index.get(&palette).unwrap(), color);
+    }
+
+    #[test]
+    fn test_paletteindex_tryfrom_invalid_index() {
+        let index = 16usize;
+
+        let result = PaletteIndex::try_from(index);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidIndex { index: i, lenght: l } if i == index && l == COLORS_IN_PALETTE
+        );
+    }
+
     #[test]
     fn test_palette_debug() {
         let palette = Palette::default();
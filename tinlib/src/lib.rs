@@ -1,5 +1,17 @@
-use std::result::Result as StdResult;
+//! Components for SN-50 Fantasy Computer implementations.
+//!
+//! The `std` feature is enabled by default. Without it the crate is `no_std`
+//! and only requires `alloc`, leaving out the `cartridge` module.
+//!
+//! The `image` feature adds conversions to and from `image` crate buffers. It
+//! requires Rust 1.88, the minimum of the `image` crate, instead of 1.77.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+extern crate alloc;
+
+use core::result::Result as StdResult;
+
+#[cfg(feature = "std")]
 pub mod cartridge;
 pub mod common;
 pub mod graphic;
@@ -8,6 +20,7 @@ pub mod map;
 
 use thiserror::Error;
 
+#[cfg(feature = "std")]
 use crate::cartridge::CartridgeError;
 use crate::common::CommonError;
 
@@ -15,6 +28,7 @@ use crate::common::CommonError;
 #[derive(Error, Debug)]
 pub enum Error {
     /// Error to wrap internal Cartridge errors.
+    #[cfg(feature = "std")]
     #[error(transparent)]
     Cartridge(#[from] CartridgeError),
     /// Error to wrap internal Common errors.
@@ -24,3 +38,26 @@ pub enum Error {
 
 /// Internal result.
 pub type Result<T> = StdResult<T, Error>;
+
+// Compile time checks of the types that can be shared between threads.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    fn assert_send<T: Send>() {}
+
+    #[cfg(feature = "std")]
+    assert_send_sync::<cartridge::Cartridge>();
+    assert_send_sync::<common::Coord>();
+    assert_send_sync::<common::Size>();
+    assert_send_sync::<graphic::Color>();
+    assert_send_sync::<graphic::Font>();
+    assert_send_sync::<graphic::Glyph>();
+    assert_send_sync::<graphic::Palette>();
+    assert_send_sync::<machine::Screen>();
+    assert_send_sync::<map::IndexMap>();
+    // Borrowed types are only `Send` and `Sync` within their lifetime.
+    assert_send_sync::<map::Map<'static>>();
+    assert_send_sync::<map::Tile<'static>>();
+    assert_send_sync::<Error>();
+    // The frame hook is only `Send`, so a Machine can be moved but not shared.
+    assert_send::<machine::Machine<'static>>();
+};
@@ -0,0 +1,141 @@
+//! BlinkCursor implementation and manipulation.
+use std::time::{Duration, Instant};
+
+use crate::common::{Coord, Result};
+use crate::graphic::Glyph;
+use crate::machine::screen::{Screen, ScreenPixel};
+
+/// A text cursor blinking at a fixed interval.
+///
+/// The time is always given by the caller, so the cursor works with any
+/// event loop and with synthetic timestamps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlinkCursor {
+    started: Instant,
+    interval: Duration,
+}
+
+impl BlinkCursor {
+    /// Creates a visible BlinkCursor, toggling every `interval` after `now`.
+    pub fn new(now: Instant, interval: Duration) -> Self {
+        Self {
+            started: now,
+            interval,
+        }
+    }
+
+    /// Returns the toggle interval.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// Makes the cursor visible again and restarts the blinking at `now`,
+    /// like after a key press.
+    pub fn reset(&mut self, now: Instant) {
+        self.started = now;
+    }
+
+    /// Returns true if the cursor is visible at `now`.
+    ///
+    /// The cursor is visible during the first interval and toggles at each
+    /// interval boundary. A zero interval never toggles.
+    pub fn visible(&self, now: Instant) -> bool {
+        let interval = self.interval.as_nanos();
+        if interval == 0 {
+            return true;
+        }
+
+        let elapsed = now.saturating_duration_since(self.started).as_nanos();
+        (elapsed / interval) % 2 == 0
+    }
+
+    /// Draws the glyph at `origin` if the cursor is visible at `now`.
+    pub fn draw(
+        &self,
+        screen: &mut Screen,
+        origin: Coord,
+        glyph: &Glyph,
+        pixel: ScreenPixel,
+        now: Instant,
+    ) -> Result<()> {
+        if !self.visible(now) {
+            return Ok(());
+        }
+
+        screen.draw_glyph(origin, glyph, pixel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graphic::GlyphPixel;
+
+    use super::*;
+
+    #[test]
+    fn test_blinkcursor_visible() {
+        let start = Instant::now();
+        let interval = Duration::from_millis(500);
+        let cursor = BlinkCursor::new(start, interval);
+
+        assert!(cursor.visible(start));
+        assert!(cursor.visible(start + Duration::from_millis(499)));
+        assert!(!cursor.visible(start + interval));
+        assert!(!cursor.visible(start + Duration::from_millis(999)));
+        assert!(cursor.visible(start + interval * 2));
+        assert!(!cursor.visible(start + interval * 3));
+    }
+
+    #[test]
+    fn test_blinkcursor_reset() {
+        let start = Instant::now();
+        let interval = Duration::from_millis(500);
+        let mut cursor = BlinkCursor::new(start, interval);
+
+        let now = start + Duration::from_millis(600);
+        assert!(!cursor.visible(now));
+
+        cursor.reset(now);
+        assert!(cursor.visible(now));
+        assert!(!cursor.visible(now + interval));
+    }
+
+    #[test]
+    fn test_blinkcursor_zero_interval() {
+        let start = Instant::now();
+        let cursor = BlinkCursor::new(start, Duration::ZERO);
+
+        assert!(cursor.visible(start + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_blinkcursor_draw() {
+        let start = Instant::now();
+        let interval = Duration::from_millis(500);
+        let cursor = BlinkCursor::new(start, interval);
+
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(0, 7), GlyphPixel::Solid)
+            .unwrap();
+        let pixel = ScreenPixel::new(255, 255, 255);
+        let coord = Coord::new(10, 27);
+
+        let mut screen = Screen::default();
+        assert!(cursor
+            .draw(
+                &mut screen,
+                Coord::new(10, 20),
+                &glyph,
+                pixel,
+                start + interval
+            )
+            .is_ok());
+        assert_eq!(screen.get_pixel(coord).unwrap(), ScreenPixel::default());
+
+        assert!(cursor
+            .draw(&mut screen, Coord::new(10, 20), &glyph, pixel, start)
+            .is_ok());
+        assert_eq!(screen.get_pixel(coord).unwrap(), pixel);
+    }
+}
@@ -1,2 +1,389 @@
-#[derive(Default)]
-pub struct Input;
+//! Input implementation and manipulation.
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+/// The default key bindings.
+const DEFAULT_BINDINGS: [(&str, Button); Button::COUNT] = [
+    ("Up", Button::Up),
+    ("Down", Button::Down),
+    ("Left", Button::Left),
+    ("Right", Button::Right),
+    ("Z", Button::A),
+    ("X", Button::B),
+    ("Enter", Button::Start),
+    ("Backspace", Button::Select),
+];
+
+/// The machine virtual buttons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Button {
+    /// The up arrow.
+    Up = 0,
+    /// The down arrow.
+    Down = 1,
+    /// The left arrow.
+    Left = 2,
+    /// The right arrow.
+    Right = 3,
+    /// The main action button.
+    A = 4,
+    /// The secondary action button.
+    B = 5,
+    /// The start button.
+    Start = 6,
+    /// The select button.
+    Select = 7,
+}
+
+impl Button {
+    /// Number of virtual buttons.
+    pub const COUNT: usize = 8;
+
+    /// All buttons, in value order.
+    pub const ALL: [Button; Button::COUNT] = [
+        Button::Up,
+        Button::Down,
+        Button::Left,
+        Button::Right,
+        Button::A,
+        Button::B,
+        Button::Start,
+        Button::Select,
+    ];
+
+    fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// A compact set of buttons, a bit per button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ButtonSet {
+    bits: u8,
+}
+
+impl ButtonSet {
+    /// Creates a ButtonSet from its bits, where bit `n` is the button with
+    /// value `n`.
+    pub fn from_bits(bits: u8) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the bits, as described in `from_bits`.
+    pub fn bits(self) -> u8 {
+        self.bits
+    }
+
+    /// Adds a button.
+    pub fn insert(&mut self, button: Button) {
+        self.bits |= button.bit();
+    }
+
+    /// Removes a button.
+    pub fn remove(&mut self, button: Button) {
+        self.bits &= !button.bit();
+    }
+
+    /// Returns true if the set has a button.
+    pub fn contains(self, button: Button) -> bool {
+        self.bits & button.bit() != 0
+    }
+
+    /// Returns the number of buttons.
+    pub fn len(self) -> usize {
+        self.bits.count_ones() as usize
+    }
+
+    /// Returns true if the set has no buttons.
+    pub fn is_empty(self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns an iterator over the buttons, in value order.
+    pub fn iter(self) -> impl Iterator<Item = Button> {
+        Button::ALL
+            .into_iter()
+            .filter(move |button| self.contains(*button))
+    }
+}
+
+/// A movement direction resolved from the held arrow buttons.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// Returns the direction as `(dx, dy)` offsets, where `y` grows down.
+    ///
+    /// The offsets can be used with `Coord::offset`.
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+            Direction::UpLeft => (-1, -1),
+            Direction::UpRight => (1, -1),
+            Direction::DownLeft => (-1, 1),
+            Direction::DownRight => (1, 1),
+        }
+    }
+}
+
+/// The machine input state.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Input {
+    held: [bool; Button::COUNT],
+}
+
+impl Input {
+    /// Marks a button as held.
+    pub fn press(&mut self, button: Button) {
+        self.held[button as usize] = true;
+    }
+
+    /// Marks a button as released.
+    pub fn release(&mut self, button: Button) {
+        self.held[button as usize] = false;
+    }
+
+    /// Returns true if a button is held.
+    pub fn is_held(&self, button: Button) -> bool {
+        self.held[button as usize]
+    }
+
+    /// Returns the held buttons.
+    pub fn snapshot(&self) -> ButtonSet {
+        let mut set = ButtonSet::default();
+        for button in Button::ALL.iter().copied().filter(|b| self.is_held(*b)) {
+            set.insert(button);
+        }
+
+        set
+    }
+
+    /// Returns the direction of the held arrow buttons.
+    ///
+    /// Two perpendicular arrows resolve to a diagonal, and opposite arrows
+    /// cancel each other. Returns `None` if there is no movement.
+    pub fn direction(&self) -> Option<Direction> {
+        let axis = |negative: Button, positive: Button| {
+            self.is_held(positive) as isize - self.is_held(negative) as isize
+        };
+
+        match (
+            axis(Button::Left, Button::Right),
+            axis(Button::Up, Button::Down),
+        ) {
+            (0, -1) => Some(Direction::Up),
+            (0, 1) => Some(Direction::Down),
+            (-1, 0) => Some(Direction::Left),
+            (1, 0) => Some(Direction::Right),
+            (-1, -1) => Some(Direction::UpLeft),
+            (1, -1) => Some(Direction::UpRight),
+            (-1, 1) => Some(Direction::DownLeft),
+            (1, 1) => Some(Direction::DownRight),
+            _ => None,
+        }
+    }
+}
+
+/// A map from platform key names to virtual buttons.
+///
+/// Key names are up to the frontend, the default bindings use the arrow keys
+/// (`Up`, `Down`, `Left` and `Right`), `Z`, `X`, `Enter` and `Backspace`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: BTreeMap<String, Button>,
+}
+
+impl Keymap {
+    /// Creates a Keymap without any binding.
+    pub fn empty() -> Self {
+        Self {
+            bindings: BTreeMap::new(),
+        }
+    }
+
+    /// Binds a key to a button, replacing any previous binding of the key.
+    ///
+    /// A button can be bound to many keys.
+    pub fn set_binding(&mut self, key: &str, button: Button) {
+        self.bindings.insert(String::from(key), button);
+    }
+
+    /// Returns the button bound to a key, or `None` if the key is unbound.
+    pub fn binding(&self, key: &str) -> Option<Button> {
+        self.bindings.get(key).copied()
+    }
+
+    /// Removes the binding of a key, returning its button.
+    pub fn remove_binding(&mut self, key: &str) -> Option<Button> {
+        self.bindings.remove(key)
+    }
+}
+
+impl Default for Keymap {
+    /// Creates a Keymap with the default bindings.
+    fn default() -> Self {
+        let mut keymap = Self::empty();
+        for (key, button) in DEFAULT_BINDINGS.iter() {
+            keymap.set_binding(key, *button);
+        }
+
+        keymap
+    }
+}
+
+/// The input state of a frame, as recorded by the machine.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InputFrame {
+    /// The frame number, counting the ticks since the machine was created.
+    pub frame: u64,
+    /// The input state during the frame.
+    pub input: Input,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_default() {
+        let input = Input::default();
+
+        assert!(!input.is_held(Button::A));
+        assert_eq!(input.direction(), None);
+    }
+
+    #[test]
+    fn test_input_press_and_release() {
+        let mut input = Input::default();
+
+        input.press(Button::A);
+        assert!(input.is_held(Button::A));
+        assert!(!input.is_held(Button::B));
+
+        input.release(Button::A);
+        assert!(!input.is_held(Button::A));
+    }
+
+    #[test]
+    fn test_input_direction_single() {
+        for (button, direction) in [
+            (Button::Up, Direction::Up),
+            (Button::Down, Direction::Down),
+            (Button::Left, Direction::Left),
+            (Button::Right, Direction::Right),
+        ] {
+            let mut input = Input::default();
+            input.press(button);
+
+            assert_eq!(input.direction(), Some(direction));
+        }
+    }
+
+    #[test]
+    fn test_input_direction_diagonal() {
+        let mut input = Input::default();
+        input.press(Button::Up);
+        input.press(Button::Right);
+
+        assert_eq!(input.direction(), Some(Direction::UpRight));
+        assert_eq!(input.direction().unwrap().offset(), (1, -1));
+    }
+
+    #[test]
+    fn test_input_direction_opposite() {
+        let mut input = Input::default();
+        input.press(Button::Left);
+        input.press(Button::Right);
+        assert_eq!(input.direction(), None);
+
+        input.press(Button::Down);
+        assert_eq!(input.direction(), Some(Direction::Down));
+    }
+
+    #[test]
+    fn test_input_snapshot() {
+        let mut input = Input::default();
+        assert!(input.snapshot().is_empty());
+
+        input.press(Button::A);
+        input.press(Button::Left);
+
+        let snapshot = input.snapshot();
+        assert_eq!(snapshot.len(), 2);
+        assert!(snapshot.contains(Button::A));
+        assert!(snapshot.contains(Button::Left));
+        assert_eq!(
+            snapshot.iter().collect::<Vec<Button>>(),
+            vec![Button::Left, Button::A]
+        );
+        assert_eq!(snapshot.bits(), 0b0001_0100);
+    }
+
+    #[test]
+    fn test_buttonset_insert_and_remove() {
+        let mut set = ButtonSet::default();
+
+        set.insert(Button::Start);
+        set.insert(Button::Start);
+        assert!(set.contains(Button::Start));
+        assert_eq!(set.len(), 1);
+
+        set.remove(Button::Start);
+        assert!(!set.contains(Button::Start));
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_buttonset_from_bits() {
+        let set = ButtonSet::from_bits(0b1000_0001);
+
+        assert_eq!(
+            set.iter().collect::<Vec<Button>>(),
+            vec![Button::Up, Button::Select]
+        );
+        assert_eq!(set.bits(), 0b1000_0001);
+    }
+
+    #[test]
+    fn test_keymap_default() {
+        let keymap = Keymap::default();
+
+        assert_eq!(keymap.binding("Up"), Some(Button::Up));
+        assert_eq!(keymap.binding("Z"), Some(Button::A));
+        assert_eq!(keymap.binding("Enter"), Some(Button::Start));
+        assert_eq!(Keymap::empty().binding("Up"), None);
+    }
+
+    #[test]
+    fn test_keymap_set_binding() {
+        let mut keymap = Keymap::default();
+
+        keymap.set_binding("Space", Button::A);
+        keymap.set_binding("Z", Button::B);
+
+        assert_eq!(keymap.binding("Space"), Some(Button::A));
+        assert_eq!(keymap.binding("Z"), Some(Button::B));
+        assert_eq!(keymap.binding("Q"), None);
+    }
+
+    #[test]
+    fn test_keymap_remove_binding() {
+        let mut keymap = Keymap::default();
+
+        assert_eq!(keymap.remove_binding("X"), Some(Button::B));
+        assert_eq!(keymap.binding("X"), None);
+        assert_eq!(keymap.remove_binding("X"), None);
+    }
+}
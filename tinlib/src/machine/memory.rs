@@ -10,7 +10,7 @@ pub struct Memory<'ram> {
 
 impl<'ram> Memory<'ram> {
     /// Returns a ram reference.
-    pub fn ram(&self) -> &RAM {
+    pub fn ram(&self) -> &RAM<'ram> {
         &self.ram
     }
 
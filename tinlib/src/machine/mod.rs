@@ -1,21 +1,35 @@
 //! Machine utilities.
 mod code;
+#[cfg(feature = "std")]
+mod cursor;
 mod input;
 mod memory;
 mod ram;
+mod rng;
 mod screen;
+mod sound;
 mod vram;
 
 pub use crate::machine::code::Code;
-pub use crate::machine::input::Input;
+#[cfg(feature = "std")]
+pub use crate::machine::cursor::BlinkCursor;
+pub use crate::machine::input::{Button, ButtonSet, Direction, Input, InputFrame, Keymap};
 pub use crate::machine::memory::Memory;
 pub use crate::machine::ram::RAM;
+pub use crate::machine::rng::Rng;
 pub use crate::machine::screen::{
-    Screen, ScreenPixel, ScreenPixelEnumerate, ScreenPixelEnumerateMut, ScreenPixelIter,
-    ScreenPixelIterMut,
+    PixelFormat, Screen, ScreenPixel, ScreenPixelEnumerate, ScreenPixelEnumerateMut,
+    ScreenPixelIter, ScreenPixelIterMut,
 };
+pub use crate::machine::sound::{Channel, Sound, Waveform, SOUND_CHANNELS};
 pub use crate::machine::vram::VRAM;
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// A hook called with the rendered screen after each frame.
+pub type FrameHook = Box<dyn FnMut(&Screen) + Send>;
+
 /// Machine states.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MachineState {
@@ -32,8 +46,12 @@ pub enum MachineState {
 /// The machine representation.
 pub struct Machine<'mem> {
     state: MachineState,
-    #[allow(dead_code)]
     memory: Memory<'mem>,
+    rng: Rng,
+    keymap: Keymap,
+    frame: u64,
+    recording: Option<Vec<InputFrame>>,
+    frame_hook: Option<FrameHook>,
 }
 
 impl<'mem> Machine<'mem> {
@@ -42,13 +60,159 @@ impl<'mem> Machine<'mem> {
         self.state
     }
 
-    pub fn load_cartridge(&mut self) {}
+    /// Returns a memory reference.
+    pub fn memory(&self) -> &Memory<'mem> {
+        &self.memory
+    }
+
+    /// Returns a mutable memory reference.
+    pub fn memory_mut(&mut self) -> &mut Memory<'mem> {
+        &mut self.memory
+    }
+
+    /// Returns a rng reference.
+    pub fn rng(&self) -> &Rng {
+        &self.rng
+    }
+
+    /// Returns a mutable rng reference.
+    pub fn rng_mut(&mut self) -> &mut Rng {
+        &mut self.rng
+    }
+
+    /// Returns the input state, kept in RAM.
+    pub fn input(&self) -> &Input {
+        self.memory.ram().input()
+    }
+
+    /// Returns a mutable input state, kept in RAM.
+    pub fn input_mut(&mut self) -> &mut Input {
+        self.memory.ram_mut().input_mut()
+    }
+
+    /// Returns the key bindings.
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Returns mutable key bindings, to load a user's keymap.
+    pub fn keymap_mut(&mut self) -> &mut Keymap {
+        &mut self.keymap
+    }
+
+    /// Marks the button bound to a key as held, returning the button, or
+    /// `None` if the key is unbound.
+    pub fn press_key(&mut self, key: &str) -> Option<Button> {
+        let button = self.keymap.binding(key)?;
+        self.input_mut().press(button);
 
-    pub fn start(&mut self) {}
+        Some(button)
+    }
+
+    /// Marks the button bound to a key as released, returning the button, or
+    /// `None` if the key is unbound.
+    pub fn release_key(&mut self, key: &str) -> Option<Button> {
+        let button = self.keymap.binding(key)?;
+        self.input_mut().release(button);
+
+        Some(button)
+    }
 
-    pub fn pause(&mut self) {}
+    /// Returns the number of frames ticked since the machine was created.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
 
-    pub fn stop(&mut self) {}
+    pub fn load_cartridge(&mut self) {
+        self.state = MachineState::Loaded;
+    }
+
+    pub fn start(&mut self) {
+        if matches!(self.state, MachineState::Loaded | MachineState::Paused) {
+            self.state = MachineState::Started;
+        }
+    }
+
+    pub fn pause(&mut self) {
+        if self.state == MachineState::Started {
+            self.state = MachineState::Paused;
+        }
+    }
+
+    /// Stops the running cart, keeping it loaded.
+    pub fn stop(&mut self) {
+        if matches!(self.state, MachineState::Started | MachineState::Paused) {
+            self.state = MachineState::Loaded;
+        }
+    }
+
+    /// Runs a frame of the started cart, then calls the frame hook with the
+    /// rendered screen.
+    ///
+    /// Does nothing unless the machine is started.
+    pub fn tick(&mut self) {
+        if self.state != MachineState::Started {
+            return;
+        }
+
+        if let Some(recording) = self.recording.as_mut() {
+            recording.push(InputFrame {
+                frame: self.frame,
+                input: *self.memory.ram().input(),
+            });
+        }
+        self.frame += 1;
+
+        if let Some(hook) = self.frame_hook.as_mut() {
+            hook(self.memory.vram().screen());
+        }
+    }
+
+    /// Starts recording the input state of each ticked frame, discarding any
+    /// previous recording.
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    /// Stops recording, returning the recorded frames, or `None` if the
+    /// machine wasn't recording.
+    pub fn stop_recording(&mut self) -> Option<Vec<InputFrame>> {
+        self.recording.take()
+    }
+
+    /// Ticks a frame for each recorded input state, in order.
+    ///
+    /// With the same rng seed, replaying a recording reproduces the recorded
+    /// session.
+    pub fn replay(&mut self, log: &[InputFrame]) {
+        for input_frame in log {
+            *self.input_mut() = input_frame.input;
+            self.tick();
+        }
+    }
+
+    /// Sets a hook called after each `tick` with the rendered screen,
+    /// replacing any previous hook.
+    ///
+    /// The screen is only borrowed during the call, so the hook must copy
+    /// any pixels it wants to keep. The hook is `'static` and `Send`, so it
+    /// can't borrow local data and shares state through `Arc`.
+    pub fn set_frame_hook(&mut self, f: FrameHook) {
+        self.frame_hook = Some(f);
+    }
+
+    /// Resets the machine as if it was just created, unloading the cart and
+    /// restoring the memory, rng and input to their defaults.
+    ///
+    /// Any recording is discarded, but the key bindings and the frame hook
+    /// are kept.
+    pub fn reset(&mut self) {
+        self.state = MachineState::Created;
+        self.memory = Memory::default();
+        self.rng = Rng::default();
+        self.frame = 0;
+        self.recording = None;
+    }
 }
 
 impl<'mem> Default for Machine<'mem> {
@@ -57,6 +221,180 @@ impl<'mem> Default for Machine<'mem> {
         Self {
             state: MachineState::Created,
             memory: Memory::default(),
+            rng: Rng::default(),
+            keymap: Keymap::default(),
+            frame: 0,
+            recording: None,
+            frame_hook: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use crate::common::Coord;
+    use crate::graphic::Color;
+
+    use super::*;
+
+    #[test]
+    fn test_machine_default() {
+        let machine = Machine::default();
+
+        assert_eq!(machine.state(), MachineState::Created);
+        assert_eq!(machine.rng(), &Rng::default());
+    }
+
+    #[test]
+    fn test_machine_states() {
+        let mut machine = Machine::default();
+
+        machine.start();
+        assert_eq!(machine.state(), MachineState::Created);
+
+        machine.load_cartridge();
+        assert_eq!(machine.state(), MachineState::Loaded);
+
+        machine.start();
+        assert_eq!(machine.state(), MachineState::Started);
+
+        machine.pause();
+        assert_eq!(machine.state(), MachineState::Paused);
+
+        machine.start();
+        assert_eq!(machine.state(), MachineState::Started);
+
+        machine.stop();
+        assert_eq!(machine.state(), MachineState::Loaded);
+    }
+
+    #[test]
+    fn test_machine_frame_hook() {
+        let frames = Arc::new(AtomicUsize::new(0));
+        let hook_frames = Arc::clone(&frames);
+
+        let mut machine = Machine::default();
+        machine.set_frame_hook(Box::new(move |screen| {
+            assert_eq!(
+                screen.get_pixel(Coord::new(0, 0)).unwrap(),
+                Color::default()
+            );
+            hook_frames.fetch_add(1, Ordering::Relaxed);
+        }));
+
+        machine.tick();
+        machine.load_cartridge();
+        machine.tick();
+        assert_eq!(frames.load(Ordering::Relaxed), 0);
+
+        machine.start();
+        for _ in 0..3 {
+            machine.tick();
         }
+        assert_eq!(frames.load(Ordering::Relaxed), 3);
+
+        machine.pause();
+        machine.tick();
+        assert_eq!(frames.load(Ordering::Relaxed), 3);
+    }
+
+    #[test]
+    fn test_machine_record_and_replay() {
+        let mut machine = Machine::default();
+        machine.rng_mut().seed(1234);
+        machine.load_cartridge();
+        machine.start();
+        machine.start_recording();
+
+        machine.input_mut().press(Button::A);
+        machine.tick();
+        machine.input_mut().press(Button::Left);
+        machine.tick();
+        machine.input_mut().release(Button::A);
+        machine.tick();
+
+        let log = machine.stop_recording().unwrap();
+        assert_eq!(log.len(), 3);
+        assert_eq!(log[0].frame, 0);
+        assert!(log[0].input.is_held(Button::A));
+        assert_eq!(log[1].input.direction(), Some(Direction::Left));
+        assert!(!log[2].input.is_held(Button::A));
+        assert_eq!(machine.stop_recording(), None);
+
+        let mut replayed = Machine::default();
+        replayed.rng_mut().seed(1234);
+        replayed.load_cartridge();
+        replayed.start();
+        replayed.start_recording();
+        replayed.replay(&log);
+
+        assert_eq!(replayed.stop_recording().unwrap(), log);
+        assert_eq!(replayed.frame(), machine.frame());
+        assert_eq!(replayed.input(), machine.input());
+        assert_eq!(replayed.rng(), machine.rng());
+        assert_eq!(
+            replayed.memory().vram().screen().as_color_slice(),
+            machine.memory().vram().screen().as_color_slice()
+        );
+    }
+
+    #[test]
+    fn test_machine_reset() {
+        let mut machine = Machine::default();
+        machine.load_cartridge();
+        machine.start();
+        machine
+            .memory_mut()
+            .vram_mut()
+            .screen_mut()
+            .set_pixel(Coord::new(1, 1), ScreenPixel::new(255, 255, 255))
+            .unwrap();
+        machine.rng_mut().seed(1234);
+
+        machine.reset();
+
+        assert_eq!(machine.state(), MachineState::Created);
+        assert!(machine
+            .memory()
+            .vram()
+            .screen()
+            .iter()
+            .all(|p| *p == ScreenPixel::default()));
+        assert_eq!(machine.rng(), &Rng::default());
+    }
+
+    #[test]
+    fn test_machine_rng_mut() {
+        let mut machine_1 = Machine::default();
+        let mut machine_2 = Machine::default();
+
+        machine_1.rng_mut().seed(1234);
+        machine_2.rng_mut().seed(1234);
+
+        for _ in 0..10 {
+            assert_eq!(
+                machine_1.rng_mut().next_u32(),
+                machine_2.rng_mut().next_u32()
+            );
+        }
+    }
+
+    #[test]
+    fn test_machine_press_key() {
+        let mut machine = Machine::default();
+        machine.keymap_mut().set_binding("Space", Button::A);
+
+        assert_eq!(machine.press_key("Space"), Some(Button::A));
+        assert!(machine.input().is_held(Button::A));
+        assert!(machine.memory().ram().input().is_held(Button::A));
+
+        assert_eq!(machine.release_key("Space"), Some(Button::A));
+        assert!(!machine.input().is_held(Button::A));
+
+        assert_eq!(machine.press_key("Q"), None);
+        assert_eq!(*machine.input(), Input::default());
     }
 }
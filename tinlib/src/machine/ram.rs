@@ -1,6 +1,7 @@
 //! RAM implementation and manipulation.
 use crate::machine::code::Code;
 use crate::machine::input::Input;
+use crate::machine::sound::Sound;
 use crate::map::Map;
 
 /// The machine RAM representation.
@@ -9,6 +10,7 @@ pub struct RAM<'map> {
     code: Code,
     map: Map<'map>,
     input: Input,
+    sound: Sound,
 }
 
 impl<'map> RAM<'map> {
@@ -23,7 +25,7 @@ impl<'map> RAM<'map> {
     }
 
     /// Returns a map reference.
-    pub fn map(&self) -> &Map {
+    pub fn map(&self) -> &Map<'map> {
         &self.map
     }
 
@@ -41,4 +43,14 @@ impl<'map> RAM<'map> {
     pub fn input_mut(&mut self) -> &mut Input {
         &mut self.input
     }
+
+    /// Returns a sound reference.
+    pub fn sound(&self) -> &Sound {
+        &self.sound
+    }
+
+    /// Returns a mutable sound reference.
+    pub fn sound_mut(&mut self) -> &mut Sound {
+        &mut self.sound
+    }
 }
@@ -0,0 +1,121 @@
+//! Rng implementation and manipulation.
+
+/// Fallback state, as xorshift never leaves the zero state.
+const NONZERO_STATE: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// A deterministic xorshift64* pseudo-random number generator.
+///
+/// The same seed always produces the same sequence, so game logic can be
+/// replayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Creates a Rng from a seed.
+    pub fn new(seed: u64) -> Self {
+        let mut rng = Self { state: 0 };
+        rng.seed(seed);
+
+        rng
+    }
+
+    /// Resets the generator state from a seed.
+    pub fn seed(&mut self, seed: u64) {
+        // Mixes the seed with splitmix64, so close seeds give distinct streams.
+        let mut z = seed.wrapping_add(NONZERO_STATE);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^= z >> 31;
+
+        self.state = if z == 0 { NONZERO_STATE } else { z };
+    }
+
+    /// Returns the next random `u32`.
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+
+        (x.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 32) as u32
+    }
+
+    /// Returns the next random value in `low..high`, or `low` if the range is empty.
+    pub fn next_range(&mut self, low: u32, high: u32) -> u32 {
+        if high <= low {
+            return low;
+        }
+
+        let span = (high - low) as u64;
+        low + ((self.next_u32() as u64 * span) >> 32) as u32
+    }
+}
+
+impl Default for Rng {
+    /// Creates a Rng seeded with 0.
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng_new_is_deterministic() {
+        let mut rng_1 = Rng::new(42);
+        let mut rng_2 = Rng::new(42);
+
+        for _ in 0..100 {
+            assert_eq!(rng_1.next_u32(), rng_2.next_u32());
+        }
+    }
+
+    #[test]
+    fn test_rng_different_seeds() {
+        let mut rng_1 = Rng::new(1);
+        let mut rng_2 = Rng::new(2);
+
+        let values_1: Vec<u32> = (0..8).map(|_| rng_1.next_u32()).collect();
+        let values_2: Vec<u32> = (0..8).map(|_| rng_2.next_u32()).collect();
+
+        assert_ne!(values_1, values_2);
+    }
+
+    #[test]
+    fn test_rng_seed_resets_sequence() {
+        let mut rng = Rng::new(7);
+        let first = rng.next_u32();
+        rng.next_u32();
+
+        rng.seed(7);
+        assert_eq!(rng.next_u32(), first);
+    }
+
+    #[test]
+    fn test_rng_next_range() {
+        let mut rng = Rng::new(3);
+
+        for _ in 0..1000 {
+            let value = rng.next_range(10, 20);
+            assert!((10..20).contains(&value));
+        }
+    }
+
+    #[test]
+    fn test_rng_next_range_empty() {
+        let mut rng = Rng::new(3);
+
+        assert_eq!(rng.next_range(5, 5), 5);
+        assert_eq!(rng.next_range(5, 1), 5);
+    }
+
+    #[test]
+    fn test_rng_default() {
+        assert_eq!(Rng::default(), Rng::new(0));
+    }
+}
@@ -1,19 +1,65 @@
 //! Screen implementation and manipulation.
-use std::fmt;
-use std::slice;
+use alloc::borrow::Cow;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::slice;
 
 use crate::common::{
-    CommonError, Coord, CoordEnumerate, CoordEnumerateMut, CoordIter, Result, Size,
+    coord_from_signed, CommonError, Coord, CoordEnumerate, CoordEnumerateMut, CoordIter, Rect,
+    Result, Size,
 };
-use crate::graphic::Color;
+use crate::graphic::{Color, Font, Glyph, GlyphPixel, Palette};
 
 /// Screen width in pixels.
 const SCREEN_WIDTH: usize = 640;
 /// Screen width in pixels.
 const SCREEN_HEIGHT: usize = 384;
+/// The max circle radius, longer than the screen diagonal. Larger circles
+/// centered on the screen draw the same pixels.
+const MAX_CIRCLE_RADIUS: usize = SCREEN_WIDTH + SCREEN_HEIGHT;
+/// The pixels of a black screen, borrowed by `Screen::empty`.
+///
+/// A const and not a static, as const fns can't borrow statics before Rust
+/// 1.83. It's only borrowed in `Screen::empty`, so it's stored once.
+#[allow(clippy::large_const_arrays)]
+const BLACK_PIXELS: [Color; SCREEN_WIDTH * SCREEN_HEIGHT] =
+    [Color::new(0, 0, 0); SCREEN_WIDTH * SCREEN_HEIGHT];
 
 /// A screen pixel or color.
 pub type ScreenPixel = Color;
+
+/// The byte layout of exported screen pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PixelFormat {
+    /// Red, green, blue and an opaque alpha, a byte each.
+    Rgba8,
+    /// Blue, green, red and an opaque alpha, a byte each.
+    Bgra8,
+    /// Red, green and blue, a byte each, without alpha.
+    Rgb8,
+}
+
+impl PixelFormat {
+    /// Returns the number of bytes per pixel.
+    pub fn bytes_per_pixel(self) -> usize {
+        match self {
+            PixelFormat::Rgba8 | PixelFormat::Bgra8 => 4,
+            PixelFormat::Rgb8 => 3,
+        }
+    }
+
+    fn extend(self, bytes: &mut Vec<u8>, pixel: ScreenPixel) {
+        let (red, green, blue) = (pixel.red(), pixel.green(), pixel.blue());
+
+        match self {
+            PixelFormat::Rgba8 => bytes.extend_from_slice(&[red, green, blue, u8::MAX]),
+            PixelFormat::Bgra8 => bytes.extend_from_slice(&[blue, green, red, u8::MAX]),
+            PixelFormat::Rgb8 => bytes.extend_from_slice(&[red, green, blue]),
+        }
+    }
+}
+
 /// A iterator over all screen pixels.
 pub type ScreenPixelIter<'iter> = slice::Iter<'iter, ScreenPixel>;
 /// A mutable iterator over all screen pixels.
@@ -24,11 +70,26 @@ pub type ScreenPixelEnumerate<'iter> = CoordEnumerate<'iter, ScreenPixel>;
 pub type ScreenPixelEnumerateMut<'iter> = CoordEnumerateMut<'iter, ScreenPixel>;
 
 /// A Screen representation with 640x384 tiles.
+///
+/// Pixels are stored on the heap, so a Screen is cheap to create and move.
+/// An `empty` Screen borrows static black pixels until its first change.
 pub struct Screen {
-    pixels: [Color; SCREEN_WIDTH * SCREEN_HEIGHT],
+    pixels: Cow<'static, [Color]>,
+    dirty: Option<Rect>,
 }
 
 impl Screen {
+    /// Creates a black Screen without allocating, so it can be used in
+    /// consts and statics.
+    ///
+    /// The pixels are copied to the heap on the first change.
+    pub const fn empty() -> Self {
+        Self {
+            pixels: Cow::Borrowed(&BLACK_PIXELS),
+            dirty: None,
+        }
+    }
+
     /// Returns the width.
     pub fn width(&self) -> usize {
         SCREEN_WIDTH
@@ -60,50 +121,383 @@ impl Screen {
             return Err(CommonError::new_invalid_coord(coord, self.size()));
         }
 
-        let index = self.get_index(coord);
-        self.pixels[index] = pixel;
+        self.put_pixel(coord, pixel);
 
         Ok(())
     }
 
+    /// Returns the smallest Rect enclosing every pixel changed since the last
+    /// `clear_dirty`, or `None` if nothing was drawn.
+    ///
+    /// Drawing calls mark the pixels they touch, and whole screen operations,
+    /// including the mutable iterators and slices, mark the whole screen.
+    pub fn dirty_rect(&self) -> Option<Rect> {
+        self.dirty
+    }
+
+    /// Forgets the changed pixels, usually after uploading them.
+    pub fn clear_dirty(&mut self) {
+        self.dirty = None;
+    }
+
+    /// Replaces every pixel with the result of `f` applied to it.
+    pub fn map_pixels<F>(&mut self, f: F)
+    where
+        F: Fn(ScreenPixel) -> ScreenPixel,
+    {
+        for pixel in self.iter_mut() {
+            *pixel = f(*pixel);
+        }
+    }
+
+    /// Replaces every pixel with the result of `f` applied to its coord and
+    /// color, for position dependent effects like scanlines.
+    pub fn shade<F>(&mut self, f: F)
+    where
+        F: Fn(Coord, ScreenPixel) -> ScreenPixel,
+    {
+        for (coord, pixel) in self.enumerate_mut() {
+            *pixel = f(coord, *pixel);
+        }
+    }
+
+    /// Shifts all pixels by an offset.
+    ///
+    /// With `wrap` the pixels leaving an edge come back on the opposite edge,
+    /// otherwise the vacated pixels are cleared to black.
+    pub fn scroll(&mut self, dx: isize, dy: isize, wrap: bool) {
+        let source = self.pixels.to_vec();
+        let (width, height) = (self.width() as isize, self.height() as isize);
+
+        // Reduce the offsets first, so any isize offset is safe to subtract.
+        let (dx, dy) = if wrap {
+            (dx.rem_euclid(width), dy.rem_euclid(height))
+        } else {
+            (dx.clamp(-width, width), dy.clamp(-height, height))
+        };
+
+        for (index, pixel) in self.iter_mut().enumerate() {
+            let x = index as isize % width - dx;
+            let y = index as isize / width - dy;
+
+            *pixel = if wrap {
+                let x = x.rem_euclid(width);
+                let y = y.rem_euclid(height);
+                source[(y * width + x) as usize]
+            } else if (0..width).contains(&x) && (0..height).contains(&y) {
+                source[(y * width + x) as usize]
+            } else {
+                ScreenPixel::default()
+            };
+        }
+    }
+
     /// Clears all pixels to black.
     pub fn clear(&mut self) {
-        for pixel in self.pixels.iter_mut() {
+        for pixel in self.iter_mut() {
             *pixel = ScreenPixel::default();
         }
     }
 
+    /// Returns a bilinear filtered color at normalized coordinates.
+    ///
+    /// The `u` and `v` values go from `0.0` to `1.0` across the screen width
+    /// and height, with pixel centers at `(x + 0.5) / width` and
+    /// `(y + 0.5) / height`. Samples beyond the edge pixels are clamped.
+    pub fn sample(&self, u: f32, v: f32) -> ScreenPixel {
+        let axis = |t: f32, lenght: usize| {
+            let position = (t * lenght as f32 - 0.5).clamp(0.0, (lenght - 1) as f32);
+            let start = position as usize;
+            let end = (start + 1).min(lenght - 1);
+
+            (start, end, position - start as f32)
+        };
+
+        let (x0, x1, fx) = axis(u, self.width());
+        let (y0, y1, fy) = axis(v, self.height());
+
+        let pixel = |x: usize, y: usize| self.pixels[self.get_index(Coord::new(x, y))];
+        let top = pixel(x0, y0).lerp(pixel(x1, y0), fx);
+        let bottom = pixel(x0, y1).lerp(pixel(x1, y1), fx);
+
+        top.lerp(bottom, fy)
+    }
+
+    /// Draws a line between two coords, inclusive.
+    pub fn draw_line(&mut self, from: Coord, to: Coord, pixel: ScreenPixel) -> Result<()> {
+        for coord in [from, to] {
+            if !self.is_coord_valid(coord) {
+                return Err(CommonError::new_invalid_coord(coord, self.size()));
+            }
+        }
+
+        for coord in from.line_to(to) {
+            self.put_pixel(coord, pixel);
+        }
+
+        Ok(())
+    }
+
+    /// Draws a circle outline using the midpoint circle algorithm.
+    ///
+    /// Pixels outside the screen are clipped. A radius of 0 sets only the center.
+    pub fn draw_circle(&mut self, center: Coord, radius: usize, pixel: ScreenPixel) -> Result<()> {
+        if !self.is_coord_valid(center) {
+            return Err(CommonError::new_invalid_coord(center, self.size()));
+        }
+
+        let (cx, cy) = (center.x as isize, center.y as isize);
+        Self::circle_octant(radius, |x, y| {
+            for (px, py) in [
+                (cx + x, cy + y),
+                (cx + y, cy + x),
+                (cx - y, cy + x),
+                (cx - x, cy + y),
+                (cx - x, cy - y),
+                (cx - y, cy - x),
+                (cx + y, cy - x),
+                (cx + x, cy - y),
+            ] {
+                self.set_pixel_clipped(px, py, pixel);
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Replaces every pixel with its nearest color in a palette.
+    pub fn quantize_to(&mut self, palette: &Palette) {
+        for pixel in self.iter_mut() {
+            *pixel = palette.colors[palette.nearest(*pixel)];
+        }
+    }
+
+    /// Draws the solid pixels of a glyph with its top left corner at `origin`.
+    ///
+    /// Pixels outside the screen are clipped.
+    pub fn draw_glyph(&mut self, origin: Coord, glyph: &Glyph, pixel: ScreenPixel) -> Result<()> {
+        self.draw_glyph_scaled(origin, glyph, pixel, 1)
+    }
+
+    /// Draws the solid pixels of a glyph as `scale` x `scale` blocks, with its
+    /// top left corner at `origin`.
+    ///
+    /// Pixels outside the screen are clipped. A scale of 0 draws nothing.
+    pub fn draw_glyph_scaled(
+        &mut self,
+        origin: Coord,
+        glyph: &Glyph,
+        pixel: ScreenPixel,
+        scale: usize,
+    ) -> Result<()> {
+        if !self.is_coord_valid(origin) {
+            return Err(CommonError::new_invalid_coord(origin, self.size()));
+        }
+
+        for (coord, glyph_pixel) in glyph.enumerate() {
+            if *glyph_pixel != GlyphPixel::Solid {
+                continue;
+            }
+
+            let x = origin.x.saturating_add(coord.x.saturating_mul(scale));
+            let y = origin.y.saturating_add(coord.y.saturating_mul(scale));
+            for by in y..y.saturating_add(scale).min(self.height()) {
+                for bx in x..x.saturating_add(scale).min(self.width()) {
+                    self.put_pixel(Coord::new(bx, by), pixel);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Draws text with proportional spacing, starting at `origin`.
+    ///
+    /// Each byte of `text` is a glyph index in `font`. Glyphs are placed by
+    /// their solid pixels and advance by `Font::advance_width`. Pixels outside
+    /// the screen are clipped.
+    ///
+    /// The baseline is a glyph height below `origin`, and glyphs are moved
+    /// down so the font's ascent ends on it, aligning fonts with different
+    /// ascents.
+    pub fn draw_text_proportional(
+        &mut self,
+        origin: Coord,
+        text: &str,
+        font: &Font,
+        pixel: ScreenPixel,
+    ) -> Result<()> {
+        if !self.is_coord_valid(origin) {
+            return Err(CommonError::new_invalid_coord(origin, self.size()));
+        }
+
+        let mut pen = origin.x as isize;
+        for index in text.bytes().map(usize::from) {
+            let glyph = font.glyph(index)?;
+            let top = (origin.y + glyph.height()) as isize - font.ascent() as isize;
+
+            if let Some((min, _)) = glyph.bounding_box() {
+                for (coord, glyph_pixel) in glyph.enumerate() {
+                    if *glyph_pixel == GlyphPixel::Solid {
+                        let x = pen + coord.x as isize - min.x as isize;
+                        let y = top + coord.y as isize;
+                        self.set_pixel_clipped(x, y, pixel);
+                    }
+                }
+            }
+
+            pen += font.advance_width(index)? as isize;
+        }
+
+        Ok(())
+    }
+
+    /// Draws a solid disc using the midpoint circle algorithm.
+    ///
+    /// Pixels outside the screen are clipped. A radius of 0 sets only the center.
+    pub fn fill_circle(&mut self, center: Coord, radius: usize, pixel: ScreenPixel) -> Result<()> {
+        if !self.is_coord_valid(center) {
+            return Err(CommonError::new_invalid_coord(center, self.size()));
+        }
+
+        let (cx, cy) = (center.x as isize, center.y as isize);
+        let right = self.width() as isize - 1;
+        Self::circle_octant(radius, |x, y| {
+            for (half, row) in [(x, cy + y), (x, cy - y), (y, cy + x), (y, cy - x)] {
+                for px in (cx - half).max(0)..=(cx + half).min(right) {
+                    self.set_pixel_clipped(px, row, pixel);
+                }
+            }
+        });
+
+        Ok(())
+    }
+
     /// Returns an iterator over all screen coords.
     pub fn coords(&self) -> CoordIter {
         CoordIter::new(self.size())
     }
 
     /// Returns an iterator over all screen pixels.
-    pub fn iter(&self) -> ScreenPixelIter {
+    pub fn iter(&self) -> ScreenPixelIter<'_> {
         self.pixels.iter()
     }
 
     /// Returns a mutable iterator over all screen pixels.
-    pub fn iter_mut(&mut self) -> ScreenPixelIterMut {
-        self.pixels.iter_mut()
+    pub fn iter_mut(&mut self) -> ScreenPixelIterMut<'_> {
+        self.mark_all_dirty();
+        self.pixels.to_mut().iter_mut()
+    }
+
+    /// Returns all screen pixels as a slice.
+    ///
+    /// Pixels are stored in row-major order, tightly packed as `Color`s, which
+    /// are 3 bytes each in red, green, blue order.
+    pub fn as_color_slice(&self) -> &[ScreenPixel] {
+        &self.pixels[..]
+    }
+
+    /// Returns all screen pixels as a mutable slice.
+    ///
+    /// See `as_color_slice` for the memory layout.
+    pub fn as_color_slice_mut(&mut self) -> &mut [ScreenPixel] {
+        self.mark_all_dirty();
+        self.pixels.to_mut()
+    }
+
+    /// Returns all screen pixels as RGBA bytes, in row-major order from the top
+    /// row, with an opaque alpha.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        self.export(PixelFormat::Rgba8)
+    }
+
+    /// Returns all screen pixels as RGBA bytes, like `to_rgba`, but starting
+    /// from the bottom row.
+    ///
+    /// Use it for surfaces that expect bottom-up row order, like OpenGL textures.
+    pub fn to_rgba_flipped(&self) -> Vec<u8> {
+        Self::rows_to_bytes(
+            self.pixels.chunks_exact(self.width()).rev(),
+            PixelFormat::Rgba8,
+        )
+    }
+
+    /// Returns all screen pixels as bytes in the given format, in row-major
+    /// order from the top row.
+    pub fn export(&self, format: PixelFormat) -> Vec<u8> {
+        Self::rows_to_bytes(self.pixels.chunks_exact(self.width()), format)
     }
 
     /// Returns an enumerate iterator over all screen pixels and tiles.
-    pub fn enumerate(&self) -> ScreenPixelEnumerate {
+    pub fn enumerate(&self) -> ScreenPixelEnumerate<'_> {
         ScreenPixelEnumerate::new(self.coords(), self.iter())
     }
 
     /// Returns a mutable enumerate iterator over all screen pixels and tiles.
-    pub fn enumerate_mut(&mut self) -> ScreenPixelEnumerateMut {
+    pub fn enumerate_mut(&mut self) -> ScreenPixelEnumerateMut<'_> {
         ScreenPixelEnumerateMut::new(self.coords(), self.iter_mut())
     }
 
+    fn set_pixel_clipped(&mut self, x: isize, y: isize, pixel: ScreenPixel) {
+        match coord_from_signed(x, y) {
+            Some(coord) if self.is_coord_valid(coord) => {
+                self.put_pixel(coord, pixel);
+            }
+            _ => {}
+        }
+    }
+
+    /// Sets a pixel at a valid coord, marking it as dirty.
+    fn put_pixel(&mut self, coord: Coord, pixel: ScreenPixel) {
+        let index = self.get_index(coord);
+        self.pixels.to_mut()[index] = pixel;
+
+        let rect = Rect::new(coord, Size::new(1, 1));
+        self.dirty = Some(self.dirty.map_or(rect, |dirty| dirty.union(rect)));
+    }
+
+    fn mark_all_dirty(&mut self) {
+        self.dirty = Some(Rect::new(Coord::new(0, 0), self.size()));
+    }
+
+    fn rows_to_bytes<'a, I>(rows: I, format: PixelFormat) -> Vec<u8>
+    where
+        I: Iterator<Item = &'a [ScreenPixel]>,
+    {
+        let mut bytes = Vec::with_capacity(SCREEN_WIDTH * SCREEN_HEIGHT * format.bytes_per_pixel());
+        for pixel in rows.flatten() {
+            format.extend(&mut bytes, *pixel);
+        }
+
+        bytes
+    }
+
+    /// Calls `f` with each point of the first octant of a circle, with the
+    /// radius clamped to `MAX_CIRCLE_RADIUS`.
+    fn circle_octant<F: FnMut(isize, isize)>(radius: usize, mut f: F) {
+        let mut x = radius.min(MAX_CIRCLE_RADIUS) as isize;
+        let mut y = 0isize;
+        let mut err = 1 - x;
+
+        while x >= y {
+            f(x, y);
+
+            y += 1;
+            if err < 0 {
+                err += 2 * y + 1;
+            } else {
+                x -= 1;
+                err += 2 * (y - x) + 1;
+            }
+        }
+    }
+
     fn is_coord_valid(&self, coord: Coord) -> bool {
         coord.x < self.width() && coord.y < self.height()
     }
 
     fn get_index(&self, coord: Coord) -> usize {
-        coord.x * self.width() + coord.y
+        coord.y * self.width() + coord.x
     }
 }
 
@@ -111,7 +505,8 @@ impl Default for Screen {
     /// Creates a new black Screen.
     fn default() -> Self {
         Self {
-            pixels: [Color::default(); SCREEN_WIDTH * SCREEN_HEIGHT],
+            pixels: Cow::Owned(vec![Color::default(); SCREEN_WIDTH * SCREEN_HEIGHT]),
+            dirty: None,
         }
     }
 }
@@ -139,6 +534,37 @@ mod tests {
         assert!(screen.pixels.iter().all(|p| *p == default_pixel));
     }
 
+    #[test]
+    fn test_screen_empty() {
+        static SCREEN: Screen = Screen::empty();
+
+        assert_eq!(SCREEN.as_color_slice().len(), SCREEN_WIDTH * SCREEN_HEIGHT);
+        assert!(SCREEN.iter().all(|p| *p == ScreenPixel::new(0, 0, 0)));
+        assert_eq!(SCREEN.dirty_rect(), None);
+    }
+
+    #[test]
+    fn test_screen_empty_set_pixel() {
+        let pixel = ScreenPixel::new(255, 255, 255);
+        let mut screen = Screen::empty();
+
+        assert!(screen.set_pixel(Coord::new(1, 2), pixel).is_ok());
+
+        assert_eq!(screen.get_pixel(Coord::new(1, 2)).unwrap(), pixel);
+        assert_eq!(screen.iter().filter(|p| **p == pixel).count(), 1);
+        assert!(Screen::empty().iter().all(|p| *p == ScreenPixel::default()));
+    }
+
+    #[test]
+    fn test_screen_default_small_stack() {
+        let handle = std::thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(|| Screen::default().pixels.len())
+            .unwrap();
+
+        assert_eq!(handle.join().unwrap(), SCREEN_WIDTH * SCREEN_HEIGHT);
+    }
+
     #[test]
     fn test_screen_width_height_and_size() {
         let screen = Screen::default();
@@ -186,6 +612,17 @@ mod tests {
         assert_eq!(result.unwrap(), pixel);
     }
 
+    #[test]
+    fn test_screen_set_pixel_last_coord() {
+        let mut screen = Screen::default();
+        let coord = Coord::new(SCREEN_WIDTH - 1, SCREEN_HEIGHT - 1);
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.set_pixel(coord, pixel);
+        assert!(result.is_ok());
+        assert_eq!(screen.pixels[SCREEN_WIDTH * SCREEN_HEIGHT - 1], pixel);
+    }
+
     #[test]
     fn test_screen_set_pixel_invalid_coord() {
         let mut screen = Screen::default();
@@ -200,6 +637,520 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_screen_sample_pixel_center() {
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(200, 100, 50);
+        screen.set_pixel(Coord::new(10, 20), pixel).unwrap();
+
+        let u = 10.5 / SCREEN_WIDTH as f32;
+        let v = 20.5 / SCREEN_HEIGHT as f32;
+
+        assert_eq!(screen.sample(u, v), pixel);
+    }
+
+    #[test]
+    fn test_screen_sample_between_pixels() {
+        let mut screen = Screen::default();
+        screen
+            .set_pixel(Coord::new(10, 20), ScreenPixel::new(200, 100, 50))
+            .unwrap();
+        screen
+            .set_pixel(Coord::new(11, 20), ScreenPixel::new(100, 50, 0))
+            .unwrap();
+
+        let u = 11.0 / SCREEN_WIDTH as f32;
+        let v = 20.5 / SCREEN_HEIGHT as f32;
+
+        assert_eq!(screen.sample(u, v), ScreenPixel::new(150, 75, 25));
+    }
+
+    #[test]
+    fn test_screen_sample_clamped() {
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(200, 100, 50);
+        screen.set_pixel(Coord::new(0, 0), pixel).unwrap();
+        screen
+            .set_pixel(Coord::new(SCREEN_WIDTH - 1, SCREEN_HEIGHT - 1), pixel)
+            .unwrap();
+
+        assert_eq!(screen.sample(-1.0, -1.0), pixel);
+        assert_eq!(screen.sample(0.0, 0.0), pixel);
+        assert_eq!(screen.sample(1.0, 1.0), pixel);
+        assert_eq!(screen.sample(2.0, 2.0), pixel);
+    }
+
+    #[test]
+    fn test_screen_draw_line() {
+        let mut screen = Screen::default();
+        let from = Coord::new(1, 1);
+        let to = Coord::new(4, 4);
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.draw_line(from, to, pixel);
+        assert!(result.is_ok());
+
+        for coord in from.line_to(to) {
+            assert_eq!(screen.get_pixel(coord).unwrap(), pixel);
+        }
+        assert_eq!(screen.iter().filter(|p| **p == pixel).count(), 4);
+    }
+
+    #[test]
+    fn test_screen_draw_line_invalid_coord() {
+        let mut screen = Screen::default();
+        let from = Coord::new(1, 1);
+        let to = Coord::new(641, 1);
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.draw_line(from, to, pixel);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidCoord { coord: c, size: s } if c == to && s == screen.size()
+        );
+    }
+
+    #[test]
+    fn test_screen_draw_circle() {
+        let mut screen = Screen::default();
+        let center = Coord::new(10, 10);
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.draw_circle(center, 3, pixel);
+        assert!(result.is_ok());
+
+        for coord in [
+            Coord::new(13, 10),
+            Coord::new(7, 10),
+            Coord::new(10, 13),
+            Coord::new(10, 7),
+        ] {
+            assert_eq!(screen.get_pixel(coord).unwrap(), pixel);
+        }
+        assert_eq!(screen.get_pixel(center).unwrap(), ScreenPixel::default());
+    }
+
+    #[test]
+    fn test_screen_draw_circle_zero_radius() {
+        let mut screen = Screen::default();
+        let center = Coord::new(10, 10);
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.draw_circle(center, 0, pixel);
+        assert!(result.is_ok());
+
+        assert_eq!(screen.get_pixel(center).unwrap(), pixel);
+        assert_eq!(screen.iter().filter(|p| **p == pixel).count(), 1);
+    }
+
+    #[test]
+    fn test_screen_draw_circle_clipped() {
+        let mut screen = Screen::default();
+        let center = Coord::new(SCREEN_WIDTH - 1, 0);
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.draw_circle(center, 5, pixel);
+        assert!(result.is_ok());
+        assert_eq!(
+            screen.get_pixel(Coord::new(SCREEN_WIDTH - 6, 0)).unwrap(),
+            pixel
+        );
+        assert_eq!(
+            screen.get_pixel(Coord::new(SCREEN_WIDTH - 1, 5)).unwrap(),
+            pixel
+        );
+    }
+
+    #[test]
+    fn test_screen_draw_circle_huge_radius() {
+        let mut screen = Screen::default();
+        let center = Coord::new(SCREEN_WIDTH - 1, SCREEN_HEIGHT - 1);
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.draw_circle(center, usize::MAX, pixel);
+        assert!(result.is_ok());
+        assert!(screen.iter().all(|p| *p == ScreenPixel::default()));
+    }
+
+    #[test]
+    fn test_screen_draw_circle_invalid_coord() {
+        let mut screen = Screen::default();
+        let center = Coord::new(641, 1);
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.draw_circle(center, 3, pixel);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidCoord { coord: c, size: s } if c == center && s == screen.size()
+        );
+    }
+
+    #[test]
+    fn test_screen_fill_circle() {
+        let mut screen = Screen::default();
+        let center = Coord::new(10, 10);
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.fill_circle(center, 3, pixel);
+        assert!(result.is_ok());
+
+        for coord in [
+            center,
+            Coord::new(13, 10),
+            Coord::new(7, 10),
+            Coord::new(10, 13),
+            Coord::new(10, 7),
+            Coord::new(11, 11),
+        ] {
+            assert_eq!(screen.get_pixel(coord).unwrap(), pixel);
+        }
+        assert_eq!(
+            screen.get_pixel(Coord::new(14, 10)).unwrap(),
+            ScreenPixel::default()
+        );
+    }
+
+    #[test]
+    fn test_screen_fill_circle_zero_radius() {
+        let mut screen = Screen::default();
+        let center = Coord::new(10, 10);
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.fill_circle(center, 0, pixel);
+        assert!(result.is_ok());
+
+        assert_eq!(screen.get_pixel(center).unwrap(), pixel);
+        assert_eq!(screen.iter().filter(|p| **p == pixel).count(), 1);
+    }
+
+    #[test]
+    fn test_screen_fill_circle_huge_radius() {
+        let mut screen = Screen::default();
+        let center = Coord::new(0, SCREEN_HEIGHT - 1);
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.fill_circle(center, usize::MAX, pixel);
+        assert!(result.is_ok());
+        assert!(screen.iter().all(|p| *p == pixel));
+    }
+
+    #[test]
+    fn test_screen_quantize_to() {
+        let black = ScreenPixel::new(0, 0, 0);
+        let white = ScreenPixel::new(255, 255, 255);
+        let mut palette = Palette::default();
+        palette.set_color(1, white).unwrap();
+
+        let colors = [
+            ScreenPixel::new(10, 200, 250),
+            ScreenPixel::new(60, 20, 90),
+            ScreenPixel::new(130, 130, 130),
+            ScreenPixel::new(120, 120, 120),
+        ];
+        let mut screen = Screen::default();
+        screen.as_color_slice_mut()[..4].copy_from_slice(&colors);
+
+        screen.quantize_to(&palette);
+
+        assert!(screen.iter().all(|p| *p == black || *p == white));
+        assert_eq!(&screen.as_color_slice()[..4], &[white, black, white, black]);
+    }
+
+    #[test]
+    fn test_screen_draw_glyph() {
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(1, 2), GlyphPixel::Solid)
+            .unwrap();
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.draw_glyph(Coord::new(10, 10), &glyph, pixel);
+        assert!(result.is_ok());
+
+        assert_eq!(screen.get_pixel(Coord::new(11, 12)).unwrap(), pixel);
+        assert_eq!(screen.iter().filter(|p| **p == pixel).count(), 1);
+    }
+
+    #[test]
+    fn test_screen_draw_glyph_scaled() {
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(1, 0), GlyphPixel::Solid)
+            .unwrap();
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.draw_glyph_scaled(Coord::new(10, 10), &glyph, pixel, 3);
+        assert!(result.is_ok());
+
+        for y in 10..13 {
+            for x in 13..16 {
+                assert_eq!(screen.get_pixel(Coord::new(x, y)).unwrap(), pixel);
+            }
+        }
+        assert_eq!(screen.iter().filter(|p| **p == pixel).count(), 9);
+    }
+
+    #[test]
+    fn test_screen_draw_glyph_scaled_clipped() {
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(0, 0), GlyphPixel::Solid)
+            .unwrap();
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(255, 255, 255);
+        let origin = Coord::new(SCREEN_WIDTH - 2, SCREEN_HEIGHT - 1);
+
+        let result = screen.draw_glyph_scaled(origin, &glyph, pixel, 4);
+        assert!(result.is_ok());
+        assert_eq!(screen.iter().filter(|p| **p == pixel).count(), 2);
+    }
+
+    #[test]
+    fn test_screen_draw_glyph_scaled_zero_scale() {
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(0, 0), GlyphPixel::Solid)
+            .unwrap();
+        let mut screen = Screen::default();
+
+        let result =
+            screen.draw_glyph_scaled(Coord::new(0, 0), &glyph, ScreenPixel::new(1, 1, 1), 0);
+        assert!(result.is_ok());
+        assert!(screen.iter().all(|p| *p == ScreenPixel::default()));
+    }
+
+    #[test]
+    fn test_screen_draw_text_proportional() {
+        let mut font = Font::default();
+        // a narrow glyph, 1 pixel wide.
+        font.glyphs[b'i' as usize]
+            .set_pixel(Coord::new(3, 0), GlyphPixel::Solid)
+            .unwrap();
+        // a wide glyph, 6 pixels wide.
+        font.glyphs[b'w' as usize]
+            .set_pixel(Coord::new(0, 0), GlyphPixel::Solid)
+            .unwrap();
+        font.glyphs[b'w' as usize]
+            .set_pixel(Coord::new(5, 0), GlyphPixel::Solid)
+            .unwrap();
+
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let result = screen.draw_text_proportional(Coord::new(10, 10), "iwi", &font, pixel);
+        assert!(result.is_ok());
+
+        for x in [10, 12, 17, 19] {
+            assert_eq!(screen.get_pixel(Coord::new(x, 10)).unwrap(), pixel);
+        }
+        assert_eq!(screen.iter().filter(|p| **p == pixel).count(), 4);
+        assert!(
+            font.advance_width(b'i' as usize).unwrap() < font.advance_width(b'w' as usize).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_screen_draw_text_proportional_baseline() {
+        let mut font = Font::default();
+        font.glyphs[b'a' as usize]
+            .set_pixel(Coord::new(0, 5), GlyphPixel::Solid)
+            .unwrap();
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let mut screen = Screen::default();
+        screen
+            .draw_text_proportional(Coord::new(10, 10), "a", &font, pixel)
+            .unwrap();
+        assert_eq!(screen.get_pixel(Coord::new(10, 15)).unwrap(), pixel);
+
+        font.set_ascent(6);
+        let mut screen = Screen::default();
+        screen
+            .draw_text_proportional(Coord::new(10, 10), "a", &font, pixel)
+            .unwrap();
+        assert_eq!(screen.get_pixel(Coord::new(10, 17)).unwrap(), pixel);
+        assert_eq!(screen.iter().filter(|p| **p == pixel).count(), 1);
+    }
+
+    #[test]
+    fn test_screen_draw_text_proportional_invalid_origin() {
+        let mut screen = Screen::default();
+        let font = Font::default();
+        let origin = Coord::new(SCREEN_WIDTH, 0);
+
+        let result = screen.draw_text_proportional(origin, "a", &font, ScreenPixel::default());
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidCoord { coord: c, size: s } if c == origin && s == screen.size()
+        );
+    }
+
+    #[test]
+    fn test_screen_dirty_rect() {
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(255, 255, 255);
+        assert_eq!(screen.dirty_rect(), None);
+
+        screen.set_pixel(Coord::new(10, 200), pixel).unwrap();
+        screen.set_pixel(Coord::new(500, 20), pixel).unwrap();
+        assert_eq!(
+            screen.dirty_rect(),
+            Some(Rect::new(Coord::new(10, 20), Size::new(491, 181)))
+        );
+
+        screen.clear_dirty();
+        assert_eq!(screen.dirty_rect(), None);
+    }
+
+    #[test]
+    fn test_screen_dirty_rect_draw() {
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        screen
+            .draw_line(Coord::new(5, 5), Coord::new(8, 6), pixel)
+            .unwrap();
+        assert_eq!(
+            screen.dirty_rect(),
+            Some(Rect::new(Coord::new(5, 5), Size::new(4, 2)))
+        );
+
+        screen.clear_dirty();
+        let result = screen.draw_glyph(Coord::new(0, 0), &Glyph::default(), pixel);
+        assert!(result.is_ok());
+        assert_eq!(screen.dirty_rect(), None);
+
+        screen.clear();
+        assert_eq!(
+            screen.dirty_rect(),
+            Some(Rect::new(Coord::new(0, 0), screen.size()))
+        );
+    }
+
+    #[test]
+    fn test_screen_map_pixels() {
+        let mut screen = Screen::default();
+        screen
+            .set_pixel(Coord::new(1, 1), ScreenPixel::new(255, 0, 0))
+            .unwrap();
+        screen
+            .set_pixel(Coord::new(2, 1), ScreenPixel::new(10, 200, 30))
+            .unwrap();
+
+        screen.map_pixels(ScreenPixel::to_grayscale);
+
+        assert!(screen
+            .iter()
+            .all(|p| p.red() == p.green() && p.green() == p.blue()));
+        assert_eq!(
+            screen.get_pixel(Coord::new(1, 1)).unwrap(),
+            ScreenPixel::new(76, 76, 76)
+        );
+    }
+
+    #[test]
+    fn test_screen_shade() {
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(200, 100, 50);
+        screen.map_pixels(|_| pixel);
+
+        screen.shade(|coord, p| {
+            if coord.y % 2 == 1 {
+                ScreenPixel::new(p.red() / 2, p.green() / 2, p.blue() / 2)
+            } else {
+                p
+            }
+        });
+
+        let even = screen.get_pixel(Coord::new(3, 4)).unwrap();
+        let odd = screen.get_pixel(Coord::new(3, 5)).unwrap();
+        assert_ne!(even, odd);
+        assert_eq!(even, pixel);
+        assert_eq!(odd, ScreenPixel::new(100, 50, 25));
+    }
+
+    #[test]
+    fn test_screen_scroll_wrap() {
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(255, 255, 255);
+        screen
+            .set_pixel(Coord::new(SCREEN_WIDTH - 1, 5), pixel)
+            .unwrap();
+        screen.set_pixel(Coord::new(3, 5), pixel).unwrap();
+
+        screen.scroll(1, 0, true);
+
+        assert_eq!(screen.get_pixel(Coord::new(0, 5)).unwrap(), pixel);
+        assert_eq!(screen.get_pixel(Coord::new(4, 5)).unwrap(), pixel);
+        assert_eq!(screen.iter().filter(|p| **p == pixel).count(), 2);
+    }
+
+    #[test]
+    fn test_screen_scroll_no_wrap() {
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(255, 255, 255);
+        for y in 0..SCREEN_HEIGHT {
+            screen.set_pixel(Coord::new(0, y), pixel).unwrap();
+            screen
+                .set_pixel(Coord::new(SCREEN_WIDTH - 1, y), pixel)
+                .unwrap();
+        }
+
+        screen.scroll(1, 0, false);
+
+        for y in 0..SCREEN_HEIGHT {
+            assert_eq!(
+                screen.get_pixel(Coord::new(0, y)).unwrap(),
+                ScreenPixel::default()
+            );
+            assert_eq!(screen.get_pixel(Coord::new(1, y)).unwrap(), pixel);
+        }
+        assert_eq!(
+            screen.iter().filter(|p| **p == pixel).count(),
+            SCREEN_HEIGHT
+        );
+    }
+
+    #[test]
+    fn test_screen_scroll_vertical_negative() {
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(255, 255, 255);
+        screen.set_pixel(Coord::new(7, 0), pixel).unwrap();
+
+        screen.scroll(0, -1, true);
+
+        assert_eq!(
+            screen.get_pixel(Coord::new(7, SCREEN_HEIGHT - 1)).unwrap(),
+            pixel
+        );
+        assert_eq!(screen.iter().filter(|p| **p == pixel).count(), 1);
+    }
+
+    #[test]
+    fn test_screen_scroll_extreme_offsets() {
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(255, 255, 255);
+        screen.set_pixel(Coord::new(7, 3), pixel).unwrap();
+
+        let mut expected = Screen::default();
+        expected.set_pixel(Coord::new(7, 3), pixel).unwrap();
+        expected.scroll(
+            isize::MIN.rem_euclid(SCREEN_WIDTH as isize),
+            isize::MAX.rem_euclid(SCREEN_HEIGHT as isize),
+            true,
+        );
+        screen.scroll(isize::MIN, isize::MAX, true);
+        assert_eq!(screen.as_color_slice(), expected.as_color_slice());
+
+        screen.scroll(isize::MAX, isize::MIN, false);
+        assert!(screen.iter().all(|p| *p == ScreenPixel::default()));
+    }
+
     #[test]
     fn test_screen_coords() {
         let screen = Screen::default();
@@ -210,10 +1161,10 @@ mod tests {
             assert_eq!(coord.x, x);
             assert_eq!(coord.y, y);
 
-            y += 1;
-            if y == screen.width() {
-                y = 0;
-                x += 1;
+            x += 1;
+            if x == screen.width() {
+                x = 0;
+                y += 1;
             }
         }
     }
@@ -242,6 +1193,78 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_screen_to_rgba() {
+        let mut screen = Screen::default();
+        screen
+            .set_pixel(Coord::new(1, 0), ScreenPixel::new(10, 20, 30))
+            .unwrap();
+
+        let bytes = screen.to_rgba();
+        assert_eq!(bytes.len(), SCREEN_WIDTH * SCREEN_HEIGHT * 4);
+        assert_eq!(&bytes[..8], &[0, 0, 0, 255, 10, 20, 30, 255]);
+    }
+
+    #[test]
+    fn test_screen_to_rgba_flipped() {
+        let mut screen = Screen::default();
+        screen
+            .set_pixel(Coord::new(1, 0), ScreenPixel::new(10, 20, 30))
+            .unwrap();
+
+        let bytes = screen.to_rgba_flipped();
+        let row_bytes = SCREEN_WIDTH * 4;
+        let last_row = &bytes[(SCREEN_HEIGHT - 1) * row_bytes..];
+
+        assert_eq!(bytes.len(), SCREEN_WIDTH * SCREEN_HEIGHT * 4);
+        assert_eq!(&last_row[4..8], &[10, 20, 30, 255]);
+        assert_eq!(&bytes[4..8], &[0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_screen_export() {
+        let mut screen = Screen::default();
+        screen
+            .set_pixel(Coord::new(1, 0), ScreenPixel::new(10, 20, 30))
+            .unwrap();
+
+        let bytes = screen.export(PixelFormat::Rgba8);
+        assert_eq!(bytes.len(), SCREEN_WIDTH * SCREEN_HEIGHT * 4);
+        assert_eq!(&bytes[..8], &[0, 0, 0, 255, 10, 20, 30, 255]);
+
+        let bytes = screen.export(PixelFormat::Bgra8);
+        assert_eq!(bytes.len(), SCREEN_WIDTH * SCREEN_HEIGHT * 4);
+        assert_eq!(&bytes[..8], &[0, 0, 0, 255, 30, 20, 10, 255]);
+
+        let bytes = screen.export(PixelFormat::Rgb8);
+        assert_eq!(bytes.len(), SCREEN_WIDTH * SCREEN_HEIGHT * 3);
+        assert_eq!(&bytes[..6], &[0, 0, 0, 10, 20, 30]);
+    }
+
+    #[test]
+    fn test_screen_as_color_slice() {
+        let mut screen = Screen::default();
+        let coord = Coord::new(3, 2);
+        let pixel = ScreenPixel::new(255, 255, 255);
+        screen.set_pixel(coord, pixel).unwrap();
+
+        let slice = screen.as_color_slice();
+        assert_eq!(slice.len(), screen.width() * screen.height());
+        assert_eq!(slice[2 * SCREEN_WIDTH + 3], pixel);
+    }
+
+    #[test]
+    fn test_screen_as_color_slice_mut() {
+        let mut screen = Screen::default();
+        let pixel = ScreenPixel::new(255, 255, 255);
+
+        let slice = screen.as_color_slice_mut();
+        assert_eq!(slice.len(), SCREEN_WIDTH * SCREEN_HEIGHT);
+        slice[SCREEN_WIDTH + 1] = pixel;
+
+        assert_eq!(screen.get_pixel(Coord::new(1, 1)).unwrap(), pixel);
+    }
+
     #[test]
     fn test_screen_enumerate() {
         let screen = Screen::default();
@@ -0,0 +1,110 @@
+//! Sound implementation and manipulation.
+use crate::common::{CommonError, Result};
+
+/// Number of sound channels.
+pub const SOUND_CHANNELS: usize = 4;
+
+/// A sound channel waveform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Waveform {
+    /// A square wave.
+    #[default]
+    Square,
+    /// A triangle wave.
+    Triangle,
+    /// A sawtooth wave.
+    Sawtooth,
+    /// White noise.
+    Noise,
+}
+
+/// The registers of a sound channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Channel {
+    /// The frequency in Hz, where `0` is silent.
+    pub frequency: u16,
+    /// The volume, where `0` is silent.
+    pub volume: u8,
+    /// The waveform.
+    pub waveform: Waveform,
+}
+
+/// The machine sound state.
+///
+/// It only holds the channel registers written by the cartridges, the audio
+/// output is up to the frontend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Sound {
+    channels: [Channel; SOUND_CHANNELS],
+}
+
+impl Sound {
+    /// Returns a channel.
+    pub fn channel(&self, index: usize) -> Result<Channel> {
+        self.channels
+            .get(index)
+            .copied()
+            .ok_or_else(|| CommonError::new_invalid_index(index, SOUND_CHANNELS))
+    }
+
+    /// Sets a channel.
+    pub fn set_channel(&mut self, index: usize, channel: Channel) -> Result<()> {
+        let slot = self
+            .channels
+            .get_mut(index)
+            .ok_or_else(|| CommonError::new_invalid_index(index, SOUND_CHANNELS))?;
+        *slot = channel;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn test_sound_default() {
+        let sound = Sound::default();
+
+        for index in 0..SOUND_CHANNELS {
+            assert_eq!(sound.channel(index).unwrap(), Channel::default());
+        }
+    }
+
+    #[test]
+    fn test_sound_set_channel() {
+        let mut sound = Sound::default();
+        let channel = Channel {
+            frequency: 440,
+            volume: 8,
+            waveform: Waveform::Triangle,
+        };
+
+        assert!(sound.set_channel(2, channel).is_ok());
+        assert_eq!(sound.channel(2).unwrap().frequency, 440);
+        assert_eq!(sound.channel(2).unwrap(), channel);
+        assert_eq!(sound.channel(1).unwrap(), Channel::default());
+    }
+
+    #[test]
+    fn test_sound_channel_invalid_index() {
+        let mut sound = Sound::default();
+
+        let result = sound.channel(SOUND_CHANNELS);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidIndex { index: i, lenght: l } if i == SOUND_CHANNELS && l == SOUND_CHANNELS
+        );
+
+        let result = sound.set_channel(SOUND_CHANNELS, Channel::default());
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidIndex { index: i, lenght: l } if i == SOUND_CHANNELS && l == SOUND_CHANNELS
+        );
+    }
+}
@@ -39,6 +39,18 @@ impl VRAM {
     pub fn font_mut(&mut self) -> &mut Font {
         &mut self.font
     }
+
+    /// Clears the screen to black, keeping the palette and font.
+    pub fn clear(&mut self) {
+        self.screen.clear();
+    }
+
+    /// Clears the screen and restores the default palette and font.
+    pub fn reset(&mut self) {
+        self.clear();
+        self.palette = Palette::default();
+        self.font = Font::default();
+    }
 }
 
 impl Default for VRAM {
@@ -51,3 +63,50 @@ impl Default for VRAM {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::common::Coord;
+    use crate::graphic::{Color, GlyphPixel};
+    use crate::machine::ScreenPixel;
+
+    use super::*;
+
+    #[test]
+    fn test_vram_clear() {
+        let mut vram = VRAM::default();
+        let color = Color::new(255, 0, 0);
+        vram.screen_mut()
+            .set_pixel(Coord::new(10, 20), color)
+            .unwrap();
+        vram.palette_mut().set_color(1, color).unwrap();
+
+        vram.clear();
+
+        assert!(vram.screen().iter().all(|p| *p == ScreenPixel::default()));
+        assert_eq!(vram.palette().get_color(1).unwrap(), color);
+    }
+
+    #[test]
+    fn test_vram_reset() {
+        let mut vram = VRAM::default();
+        let color = Color::new(255, 0, 0);
+        vram.screen_mut()
+            .set_pixel(Coord::new(10, 20), color)
+            .unwrap();
+        vram.palette_mut().set_color(1, color).unwrap();
+        vram.font_mut().glyphs[3]
+            .set_pixel(Coord::new(0, 0), GlyphPixel::Solid)
+            .unwrap();
+
+        vram.reset();
+
+        assert!(vram.screen().iter().all(|p| *p == ScreenPixel::default()));
+        assert_eq!(vram.palette().get_color(1).unwrap(), Color::default());
+        assert!(vram
+            .font()
+            .changed_glyphs(&Font::default())
+            .unwrap()
+            .is_empty());
+    }
+}
@@ -0,0 +1,31 @@
+//! FloodFillProgress implementation and manipulation.
+use alloc::vec::Vec;
+
+use crate::common::Coord;
+use crate::map::Tile;
+
+/// The state of an incremental map flood fill.
+///
+/// Returned by `Map::flood_fill_steps` and advanced with
+/// `Map::resume_flood_fill`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FloodFillProgress<'tile> {
+    /// The tile being replaced.
+    pub(crate) target: Option<Tile<'tile>>,
+    /// The tile being filled.
+    pub(crate) tile: Tile<'tile>,
+    /// The filled coords whose neighbours weren't visited yet.
+    pub(crate) frontier: Vec<Coord>,
+}
+
+impl<'tile> FloodFillProgress<'tile> {
+    /// Returns true if the fill is complete.
+    pub fn is_done(&self) -> bool {
+        self.frontier.is_empty()
+    }
+
+    /// Returns the filled coords whose neighbours weren't visited yet.
+    pub fn frontier(&self) -> &[Coord] {
+        &self.frontier
+    }
+}
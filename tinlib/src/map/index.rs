@@ -0,0 +1,240 @@
+//! IndexMap implementation and manipulation.
+use alloc::boxed::Box;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::slice;
+
+use crate::common::{
+    CommonError, Coord, CoordEnumerate, CoordEnumerateMut, CoordIter, Result, Size,
+};
+use crate::map::{MAP_HEIGHT, MAP_WIDTH};
+
+/// A Tile representation with a font glyph index and a palette color index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IndexTile {
+    /// The glyph index in a Font.
+    pub glyph: u8,
+    /// The color index in a Palette.
+    pub color: u8,
+}
+
+impl IndexTile {
+    /// Creates a new IndexTile with a glyph and a color index.
+    pub fn new(glyph: u8, color: u8) -> Self {
+        Self { glyph, color }
+    }
+}
+
+/// A iterator over all index map tiles.
+pub type IndexMapTileIter<'iter> = slice::Iter<'iter, Option<IndexTile>>;
+/// A mutable iterator over all index map tiles.
+pub type IndexMapTileIterMut<'iter> = slice::IterMut<'iter, Option<IndexTile>>;
+/// A enumeration iterator over all index map tiles and their coords.
+pub type IndexMapTileEnumerate<'iter> = CoordEnumerate<'iter, Option<IndexTile>>;
+/// A mutable enumeration iterator over all index map tiles and their coords.
+pub type IndexMapTileEnumerateMut<'iter> = CoordEnumerateMut<'iter, Option<IndexTile>>;
+
+/// A Map representation with 320x192 owned index tiles.
+///
+/// Unlike `Map`, it doesn't borrow a Font or a Palette, so it can be stored
+/// or sent to other threads freely.
+pub struct IndexMap {
+    /// Map's tiles.
+    pub tiles: Box<[Option<IndexTile>; MAP_WIDTH * MAP_HEIGHT]>,
+}
+
+impl IndexMap {
+    /// Returns the width.
+    pub fn width(&self) -> usize {
+        MAP_WIDTH
+    }
+
+    /// Returns the height.
+    pub fn height(&self) -> usize {
+        MAP_HEIGHT
+    }
+
+    /// Returns the size.
+    pub fn size(&self) -> Size {
+        Size::new(self.width(), self.height())
+    }
+
+    /// Returns a tile.
+    pub fn get_tile(&self, coord: Coord) -> Result<Option<IndexTile>> {
+        if !self.is_coord_valid(coord) {
+            return Err(CommonError::new_invalid_coord(coord, self.size()));
+        }
+
+        let index = self.get_index(coord);
+        Ok(self.tiles[index])
+    }
+
+    /// Sets a tile.
+    pub fn set_tile(&mut self, coord: Coord, value: IndexTile) -> Result<()> {
+        if !self.is_coord_valid(coord) {
+            return Err(CommonError::new_invalid_coord(coord, self.size()));
+        }
+
+        let index = self.get_index(coord);
+        self.tiles[index] = Some(value);
+
+        Ok(())
+    }
+
+    /// Returns how many set tiles use each glyph, by font glyph index.
+    pub fn count_by_glyph(&self) -> Vec<usize> {
+        let mut counts = vec![0; u8::MAX as usize + 1];
+
+        for tile in self.iter().flatten() {
+            counts[tile.glyph as usize] += 1;
+        }
+
+        counts
+    }
+
+    /// Returns an iterator over all map coords.
+    pub fn coords(&self) -> CoordIter {
+        CoordIter::new(self.size())
+    }
+
+    /// Returns an iterator over all map tiles.
+    pub fn iter(&self) -> IndexMapTileIter<'_> {
+        self.tiles.iter()
+    }
+
+    /// Returns a mutable iterator over all map tiles.
+    pub fn iter_mut(&mut self) -> IndexMapTileIterMut<'_> {
+        self.tiles.iter_mut()
+    }
+
+    /// Returns an enumerate iterator over all map coords and tiles.
+    pub fn enumerate(&self) -> IndexMapTileEnumerate<'_> {
+        IndexMapTileEnumerate::new(self.coords(), self.iter())
+    }
+
+    /// Returns a mutable enumerate iterator over all map coords and tiles.
+    pub fn enumerate_mut(&mut self) -> IndexMapTileEnumerateMut<'_> {
+        IndexMapTileEnumerateMut::new(self.coords(), self.iter_mut())
+    }
+
+    fn is_coord_valid(&self, coord: Coord) -> bool {
+        coord.x < self.width() && coord.y < self.height()
+    }
+
+    fn get_index(&self, coord: Coord) -> usize {
+        coord.y * self.width() + coord.x
+    }
+}
+
+impl Default for IndexMap {
+    /// Creates a new empty IndexMap.
+    fn default() -> Self {
+        // Built through a Vec to avoid a large temporary array on the stack.
+        let tiles = vec![None; MAP_WIDTH * MAP_HEIGHT]
+            .into_boxed_slice()
+            .try_into()
+            .expect("map buffer has the map lenght");
+
+        Self { tiles }
+    }
+}
+
+impl fmt::Debug for IndexMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tiles: Vec<&Option<IndexTile>> = self.tiles.iter().collect();
+
+        f.debug_struct("IndexMap").field("tiles", &tiles).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    #[test]
+    fn test_indextile_new() {
+        let tile = IndexTile::new(65, 3);
+
+        assert_eq!(tile.glyph, 65);
+        assert_eq!(tile.color, 3);
+    }
+
+    #[test]
+    fn test_indexmap_default() {
+        let map = IndexMap::default();
+
+        assert_eq!(map.tiles.len(), MAP_WIDTH * MAP_HEIGHT);
+        assert!(map.iter().all(|t| t.is_none()));
+        assert_eq!(map.size(), Size::new(MAP_WIDTH, MAP_HEIGHT));
+    }
+
+    #[test]
+    fn test_indexmap_set_and_get_tile() {
+        let mut map = IndexMap::default();
+        let coord = Coord::new(5, 3);
+        let tile = IndexTile::new(65, 3);
+
+        let result = map.set_tile(coord, tile);
+        assert!(result.is_ok());
+
+        let result = map.get_tile(coord);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Some(tile));
+        assert_eq!(map.tiles[3 * MAP_WIDTH + 5], Some(tile));
+    }
+
+    #[test]
+    fn test_indexmap_set_tile_invalid_coord() {
+        let mut map = IndexMap::default();
+        let coord = Coord::new(MAP_WIDTH, 0);
+
+        let result = map.set_tile(coord, IndexTile::new(0, 0));
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidCoord { coord: c, size: s } if c == coord && s == map.size()
+        );
+    }
+
+    #[test]
+    fn test_indexmap_count_by_glyph() {
+        let mut map = IndexMap::default();
+        map.set_tile(Coord::new(0, 0), IndexTile::new(65, 1))
+            .unwrap();
+        map.set_tile(Coord::new(1, 0), IndexTile::new(65, 2))
+            .unwrap();
+        map.set_tile(Coord::new(2, 0), IndexTile::new(66, 1))
+            .unwrap();
+
+        let counts = map.count_by_glyph();
+        assert_eq!(counts.len(), 256);
+        assert_eq!(counts[65], 2);
+        assert_eq!(counts[66], 1);
+        assert_eq!(counts.iter().sum::<usize>(), 3);
+    }
+
+    #[test]
+    fn test_indexmap_enumerate() {
+        let map = IndexMap::default();
+        let mut coorditer = map.coords();
+
+        for (coord, tile) in map.enumerate() {
+            assert_eq!(coord, coorditer.next().unwrap());
+            assert!(tile.is_none());
+        }
+    }
+
+    #[test]
+    fn test_indexmap_send_to_thread() {
+        let mut map = IndexMap::default();
+        map.set_tile(Coord::new(1, 1), IndexTile::new(1, 1))
+            .unwrap();
+
+        let handle = std::thread::spawn(move || map.get_tile(Coord::new(1, 1)).unwrap());
+
+        assert_eq!(handle.join().unwrap(), Some(IndexTile::new(1, 1)));
+    }
+}
@@ -1,16 +1,36 @@
 //! Map utilities.
-use std::fmt;
-use std::slice;
+mod fill;
+mod index;
+
+pub use crate::map::fill::FloodFillProgress;
+
+pub use crate::map::index::{
+    IndexMap, IndexMapTileEnumerate, IndexMapTileEnumerateMut, IndexMapTileIter,
+    IndexMapTileIterMut, IndexTile,
+};
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ptr;
+use core::slice;
 
 use crate::common::{
-    CommonError, Coord, CoordEnumerate, CoordEnumerateMut, CoordIter, Result, Size,
+    CommonError, Coord, CoordEnumerate, CoordEnumerateMut, CoordIter, Rect, Result, Size,
 };
-use crate::graphic::{Color, Glyph};
+use crate::graphic::{Color, Font, Glyph, Palette};
 
 /// Map width in Glyphs.
-const MAP_WIDTH: usize = 320;
+pub(crate) const MAP_WIDTH: usize = 320;
 /// Map height in Glyphs.
-const MAP_HEIGHT: usize = 192;
+pub(crate) const MAP_HEIGHT: usize = 192;
+/// The tiles of an empty map, borrowed by `Map::empty`.
+///
+/// Const fns can only borrow statics since Rust 1.83, so it's a const.
+#[allow(clippy::large_const_arrays)]
+const EMPTY_TILES: [Option<Tile<'static>>; MAP_WIDTH * MAP_HEIGHT] = [None; MAP_WIDTH * MAP_HEIGHT];
 
 /// A Tile representation with a glyph and a color.
 #[derive(Clone, Copy, PartialEq)]
@@ -41,18 +61,36 @@ impl<'refs> fmt::Debug for Tile<'refs> {
 pub type MapTileIter<'iter, 'tile> = slice::Iter<'iter, Option<Tile<'tile>>>;
 /// A mutable iterator over all map tiles.
 pub type MapTileIterMut<'iter, 'tile> = slice::IterMut<'iter, Option<Tile<'tile>>>;
+/// A iterator over all map rows, as slices of tiles.
+pub type MapTileRows<'iter, 'tile> = slice::ChunksExact<'iter, Option<Tile<'tile>>>;
 /// A enumeration iterator over all map tiles and their coords.
 pub type MapTileEnumerate<'iter, 'tile> = CoordEnumerate<'iter, Option<Tile<'tile>>>;
 /// A mutable enumeration iterator over all map tiles and their coords.
 pub type MapTileEnumerateMut<'iter, 'tile> = CoordEnumerateMut<'iter, Option<Tile<'tile>>>;
 
 /// A Map representation with 320x192 tiles.
+///
+/// Tiles are stored on the heap, so a Map is cheap to create and move. A Map is
+/// `Send` and `Sync`, but borrows its glyphs and colors. Use an `IndexMap` to
+/// keep a map beyond the lifetime of its Font and Palette.
 pub struct Map<'tile> {
-    /// Map's tiles.
-    pub tiles: [Option<Tile<'tile>>; MAP_WIDTH * MAP_HEIGHT],
+    /// Map's tiles, in row-major order.
+    ///
+    /// An `empty` Map borrows static empty tiles, use `to_mut` to change them.
+    pub tiles: Cow<'tile, [Option<Tile<'tile>>]>,
 }
 
 impl<'tile> Map<'tile> {
+    /// Creates an empty Map without allocating, so it can be used in consts
+    /// and statics.
+    ///
+    /// The tiles are copied to the heap on the first change.
+    pub const fn empty() -> Self {
+        Self {
+            tiles: Cow::Borrowed(&EMPTY_TILES),
+        }
+    }
+
     /// Returns the width.
     pub fn width(&self) -> usize {
         MAP_WIDTH
@@ -85,28 +123,316 @@ impl<'tile> Map<'tile> {
         }
 
         let index = self.get_index(coord);
-        self.tiles[index] = Some(value);
+        self.tiles.to_mut()[index] = Some(value);
+
+        Ok(())
+    }
+
+    /// Sets a tile from the index of its glyph in a Font and of its color in
+    /// a Palette.
+    pub fn set_tile_indexed(
+        &mut self,
+        coord: Coord,
+        glyph_index: usize,
+        color_index: usize,
+        font: &'tile Font,
+        palette: &'tile Palette,
+    ) -> Result<()> {
+        let glyph = font
+            .glyphs
+            .get(glyph_index)
+            .ok_or_else(|| CommonError::new_invalid_index(glyph_index, font.lenght()))?;
+        let color = palette
+            .colors
+            .get(color_index)
+            .ok_or_else(|| CommonError::new_invalid_index(color_index, palette.lenght()))?;
+
+        self.set_tile(coord, Tile::new(glyph, color))
+    }
+
+    /// Sets a brush of tiles, with coords relative to `origin`.
+    ///
+    /// All coords are validated before any tile is set, so an invalid brush
+    /// leaves the map untouched.
+    pub fn stamp(&mut self, origin: Coord, tiles: &[(Coord, Tile<'tile>)]) -> Result<()> {
+        let absolute = |coord: Coord| {
+            Coord::new(
+                origin.x.saturating_add(coord.x),
+                origin.y.saturating_add(coord.y),
+            )
+        };
+
+        if let Some((coord, _)) = tiles
+            .iter()
+            .find(|(coord, _)| !self.is_coord_valid(absolute(*coord)))
+        {
+            return Err(CommonError::new_invalid_coord(
+                absolute(*coord),
+                self.size(),
+            ));
+        }
+
+        for (coord, tile) in tiles.iter() {
+            let index = self.get_index(absolute(*coord));
+            self.tiles.to_mut()[index] = Some(*tile);
+        }
+
+        Ok(())
+    }
+
+    /// Copies the set tiles of another Map, with coords relative to `origin`.
+    ///
+    /// Empty tiles are skipped and tiles falling outside the map are clipped.
+    pub fn blit(&mut self, origin: Coord, other: &Map<'tile>) {
+        for (coord, tile) in other.enumerate() {
+            let tile = match tile {
+                Some(tile) => *tile,
+                None => continue,
+            };
+
+            let target = Coord::new(
+                origin.x.saturating_add(coord.x),
+                origin.y.saturating_add(coord.y),
+            );
+            if self.is_coord_valid(target) {
+                let index = self.get_index(target);
+                self.tiles.to_mut()[index] = Some(tile);
+            }
+        }
+    }
+
+    /// Clears all tiles.
+    pub fn clear(&mut self) {
+        self.tiles.to_mut().fill(None);
+    }
+
+    /// Fills the area connected to `start` with `tile`.
+    ///
+    /// The area is every tile equal to the `start` one, connected
+    /// horizontally or vertically.
+    pub fn flood_fill(&mut self, start: Coord, tile: Tile<'tile>) -> Result<()> {
+        let mut progress = self.flood_fill_steps(start, tile, usize::MAX)?;
+        while !self.resume_flood_fill(&mut progress, usize::MAX) {}
 
         Ok(())
     }
 
+    /// Starts a flood fill like `flood_fill`, visiting up to `max_cells`
+    /// filled tiles.
+    ///
+    /// The returned progress continues the fill with `resume_flood_fill`.
+    /// Changing the map between steps may leave the area partially filled.
+    pub fn flood_fill_steps(
+        &mut self,
+        start: Coord,
+        tile: Tile<'tile>,
+        max_cells: usize,
+    ) -> Result<FloodFillProgress<'tile>> {
+        let target = self.get_tile(start)?;
+
+        let mut progress = FloodFillProgress {
+            target,
+            tile,
+            frontier: Vec::new(),
+        };
+        if target != Some(tile) {
+            let index = self.get_index(start);
+            self.tiles.to_mut()[index] = Some(tile);
+            progress.frontier.push(start);
+        }
+
+        self.resume_flood_fill(&mut progress, max_cells);
+
+        Ok(progress)
+    }
+
+    /// Continues a flood fill, visiting up to `max_cells` filled tiles.
+    ///
+    /// Returns true if the fill is complete.
+    pub fn resume_flood_fill(
+        &mut self,
+        progress: &mut FloodFillProgress<'tile>,
+        max_cells: usize,
+    ) -> bool {
+        for _ in 0..max_cells {
+            let coord = match progress.frontier.pop() {
+                Some(coord) => coord,
+                None => break,
+            };
+
+            for (dx, dy) in [(0, -1), (-1, 0), (1, 0), (0, 1)] {
+                let neighbour = match coord.offset(dx, dy) {
+                    Some(neighbour) if self.is_coord_valid(neighbour) => neighbour,
+                    _ => continue,
+                };
+
+                let index = self.get_index(neighbour);
+                if self.tiles[index] == progress.target {
+                    self.tiles.to_mut()[index] = Some(progress.tile);
+                    progress.frontier.push(neighbour);
+                }
+            }
+        }
+
+        progress.is_done()
+    }
+
+    /// Returns a Map mirrored left to right.
+    ///
+    /// The new Map borrows the same glyphs and colors.
+    pub fn flipped_horizontal(&self) -> Map<'tile> {
+        let mut map = Map::default();
+
+        for (coord, tile) in self.enumerate() {
+            let index = map.get_index(Coord::new(self.width() - 1 - coord.x, coord.y));
+            map.tiles.to_mut()[index] = *tile;
+        }
+
+        map
+    }
+
+    /// Returns a Map with the top left square rotated 90 degrees clockwise.
+    ///
+    /// The map is 320x192, so only the 192x192 square at its left can rotate
+    /// without changing its size. Tiles outside the square are dropped, and
+    /// the new Map borrows the same glyphs and colors.
+    pub fn rotated_cw(&self) -> Map<'tile> {
+        let side = self.width().min(self.height());
+        let mut map = Map::default();
+
+        for (coord, tile) in self.enumerate() {
+            if coord.x >= side || coord.y >= side {
+                continue;
+            }
+
+            let index = map.get_index(Coord::new(side - 1 - coord.y, coord.x));
+            map.tiles.to_mut()[index] = *tile;
+        }
+
+        map
+    }
+
+    /// Returns an iterator over the set tiles in a view of `view` tiles with
+    /// its top-left corner at `camera`, and their coords relative to it.
+    ///
+    /// The view is clipped to the map, so tiles off the view or off the map
+    /// are never visited.
+    pub fn visible_tiles(
+        &self,
+        camera: Coord,
+        view: Size,
+    ) -> impl Iterator<Item = (Coord, Tile<'tile>)> + '_ {
+        let clipped = Size::new(
+            view.width().min(self.width().saturating_sub(camera.x)),
+            view.height().min(self.height().saturating_sub(camera.y)),
+        );
+
+        Rect::new(camera, clipped)
+            .coords()
+            .filter_map(move |coord| {
+                self.tiles[self.get_index(coord)]
+                    .map(|tile| (Coord::new(coord.x - camera.x, coord.y - camera.y), tile))
+            })
+    }
+
+    /// Returns the first set tile, and its coord, matching a predicate.
+    pub fn find<F>(&self, predicate: F) -> Option<(Coord, Tile<'tile>)>
+    where
+        F: Fn(Coord, Tile<'tile>) -> bool,
+    {
+        self.enumerate()
+            .filter_map(|(coord, tile)| tile.map(|t| (coord, t)))
+            .find(|(coord, tile)| predicate(*coord, *tile))
+    }
+
+    /// Returns how many set tiles use each palette color, by palette index.
+    ///
+    /// Tiles with colors not in the palette are ignored.
+    pub fn palette_histogram(&self, palette: &Palette) -> Vec<usize> {
+        let mut histogram = vec![0; palette.lenght()];
+
+        for tile in self.iter().flatten() {
+            if let Some(index) = palette.index_of(*tile.color) {
+                histogram[index] += 1;
+            }
+        }
+
+        histogram
+    }
+
+    /// Converts the map to an IndexMap, resolving each tile to its glyph index
+    /// in `font` and color index in `palette`.
+    ///
+    /// Glyphs borrowed from `font` are resolved by identity, other glyphs and
+    /// colors by the first equal one. Returns an error for tiles that can't be
+    /// resolved.
+    pub fn to_owned_indices(&self, font: &Font, palette: &Palette) -> Result<IndexMap> {
+        let mut map = IndexMap::default();
+
+        for (coord, tile) in self.enumerate() {
+            let tile = match tile {
+                Some(tile) => tile,
+                None => continue,
+            };
+
+            let glyph = font
+                .iter()
+                .position(|glyph| ptr::eq(glyph, tile.glyph))
+                .or_else(|| font.iter().position(|glyph| glyph == tile.glyph));
+            let color = palette.index_of(*tile.color);
+
+            match (glyph, color) {
+                (Some(glyph), Some(color)) => {
+                    map.set_tile(coord, IndexTile::new(glyph as u8, color as u8))?;
+                }
+                _ => return Err(CommonError::new_unresolved_tile(coord)),
+            }
+        }
+
+        Ok(map)
+    }
+
+    /// Renders the map as text, one row per line.
+    ///
+    /// Set tiles are rendered with `charset` and empty tiles as a space.
+    pub fn to_ascii<F>(&self, charset: F) -> String
+    where
+        F: Fn(Tile<'tile>) -> char,
+    {
+        let mut result = String::with_capacity((self.width() + 1) * self.height());
+
+        for (coord, tile) in self.enumerate() {
+            if coord.x == 0 && coord.y > 0 {
+                result.push('\n');
+            }
+            result.push(tile.map_or(' ', &charset));
+        }
+
+        result
+    }
+
     /// Returns an iterator over all map coords.
     pub fn coords(&self) -> CoordIter {
         CoordIter::new(self.size())
     }
 
     /// Returns an iterator over all map tiles.
-    pub fn iter(&self) -> MapTileIter {
+    pub fn iter(&self) -> MapTileIter<'_, 'tile> {
         self.tiles.iter()
     }
 
+    /// Returns an iterator over all map rows, from top to bottom.
+    pub fn rows(&self) -> MapTileRows<'_, 'tile> {
+        self.tiles.chunks_exact(self.width())
+    }
+
     /// Returns a mutable iterator over all map tiles.
     pub fn iter_mut<'iter>(&'iter mut self) -> MapTileIterMut<'iter, 'tile> {
-        self.tiles.iter_mut()
+        self.tiles.to_mut().iter_mut()
     }
 
     /// Returns an enumerate iterator over all map coords and tiles.
-    pub fn enumerate(&self) -> MapTileEnumerate {
+    pub fn enumerate(&self) -> MapTileEnumerate<'_, 'tile> {
         MapTileEnumerate::new(self.coords(), self.iter())
     }
 
@@ -115,12 +441,27 @@ impl<'tile> Map<'tile> {
         MapTileEnumerateMut::new(self.coords(), self.iter_mut())
     }
 
+    /// Returns an iterator over the set tiles and their coords, skipping empty ones.
+    pub fn iter_set(&self) -> impl Iterator<Item = (Coord, &Tile<'tile>)> {
+        self.enumerate()
+            .filter_map(|(coord, tile)| tile.as_ref().map(|tile| (coord, tile)))
+    }
+
+    /// Returns a mutable iterator over the set tiles and their coords, skipping
+    /// empty ones.
+    pub fn iter_mut_set<'iter>(
+        &'iter mut self,
+    ) -> impl Iterator<Item = (Coord, &'iter mut Tile<'tile>)> {
+        self.enumerate_mut()
+            .filter_map(|(coord, tile)| tile.as_mut().map(|tile| (coord, tile)))
+    }
+
     fn is_coord_valid(&self, coord: Coord) -> bool {
         coord.x < self.width() && coord.y < self.height()
     }
 
     fn get_index(&self, coord: Coord) -> usize {
-        coord.x * self.width() + coord.y
+        coord.y * self.width() + coord.x
     }
 }
 
@@ -128,7 +469,7 @@ impl<'tile> Default for Map<'tile> {
     /// Creates a new empty Map.
     fn default() -> Self {
         Self {
-            tiles: [None; MAP_WIDTH * MAP_HEIGHT],
+            tiles: Cow::Owned(vec![None; MAP_WIDTH * MAP_HEIGHT]),
         }
     }
 }
@@ -145,6 +486,8 @@ impl<'tile> fmt::Debug for Map<'tile> {
 mod tests {
     use assert_matches::assert_matches;
 
+    use crate::graphic::GlyphPixel;
+
     use super::*;
 
     #[test]
@@ -178,6 +521,16 @@ mod tests {
         assert_eq!(map.tiles.len(), MAP_WIDTH * MAP_HEIGHT);
     }
 
+    #[test]
+    fn test_map_default_small_stack() {
+        let handle = std::thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(|| Map::default().tiles.len())
+            .unwrap();
+
+        assert_eq!(handle.join().unwrap(), MAP_WIDTH * MAP_HEIGHT);
+    }
+
     #[test]
     fn test_map_width_height_and_size() {
         let map = Map::default();
@@ -210,6 +563,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_map_empty() {
+        static MAP: Map<'static> = Map::empty();
+
+        assert_eq!(MAP.tiles.len(), MAP_WIDTH * MAP_HEIGHT);
+        assert!(MAP.iter().all(Option::is_none));
+    }
+
+    #[test]
+    fn test_map_empty_set_tile() {
+        let glyph = Glyph::default();
+        let color = Color::default();
+        let tile = Tile::new(&glyph, &color);
+
+        let mut map = Map::empty();
+        assert!(map.set_tile(Coord::new(1, 1), tile).is_ok());
+
+        assert_eq!(map.get_tile(Coord::new(1, 1)).unwrap(), Some(tile));
+        assert_eq!(map.iter_set().count(), 1);
+        assert!(Map::empty().iter().all(Option::is_none));
+    }
+
     #[test]
     fn test_map_set_tile() {
         let glyph = Glyph::default();
@@ -246,6 +621,424 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_map_set_tile_indexed() {
+        let mut font = Font::default();
+        let mut glyph = Glyph::default();
+        glyph
+            .set_pixel(Coord::new(0, 0), GlyphPixel::Solid)
+            .unwrap();
+        font.set_glyph(65, glyph).unwrap();
+        let palette = Palette::standard();
+
+        let coord = Coord::new(1, 1);
+        let mut map = Map::default();
+
+        let result = map.set_tile_indexed(coord, 65, 4, &font, &palette);
+        assert!(result.is_ok());
+
+        let tile = map.get_tile(coord).unwrap().unwrap();
+        assert_eq!(tile.glyph, &glyph);
+        assert_eq!(*tile.color, palette.get_color(4).unwrap());
+    }
+
+    #[test]
+    fn test_map_set_tile_indexed_invalid() {
+        let font = Font::default();
+        let palette = Palette::default();
+        let mut map = Map::default();
+
+        let result = map.set_tile_indexed(Coord::new(0, 0), 256, 0, &font, &palette);
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidIndex {
+                index: 256,
+                lenght: 256
+            }
+        );
+
+        let result = map.set_tile_indexed(Coord::new(0, 0), 0, 16, &font, &palette);
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidIndex {
+                index: 16,
+                lenght: 16
+            }
+        );
+
+        let coord = Coord::new(0, 192);
+        let result = map.set_tile_indexed(coord, 0, 0, &font, &palette);
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidCoord { coord: c, .. } if c == coord
+        );
+    }
+
+    #[test]
+    fn test_map_stamp() {
+        let glyph = Glyph::default();
+        let color = Color::default();
+        let tile = Tile::new(&glyph, &color);
+
+        let mut map = Map::default();
+        let brush = [
+            (Coord::new(0, 0), tile),
+            (Coord::new(1, 0), tile),
+            (Coord::new(0, 1), tile),
+            (Coord::new(1, 1), tile),
+        ];
+
+        let result = map.stamp(Coord::new(10, 10), &brush);
+        assert!(result.is_ok());
+
+        for coord in [
+            Coord::new(10, 10),
+            Coord::new(11, 10),
+            Coord::new(10, 11),
+            Coord::new(11, 11),
+        ] {
+            assert_eq!(map.get_tile(coord).unwrap(), Some(tile));
+        }
+        assert_eq!(map.iter().filter(|t| t.is_some()).count(), 4);
+    }
+
+    #[test]
+    fn test_map_clear() {
+        let glyph = Glyph::default();
+        let color = Color::default();
+        let tile = Tile::new(&glyph, &color);
+
+        let mut map = Map::default();
+        map.set_tile(Coord::new(0, 0), tile).unwrap();
+        map.set_tile(Coord::new(MAP_WIDTH - 1, MAP_HEIGHT - 1), tile)
+            .unwrap();
+
+        map.clear();
+        assert!(map.iter().all(|tile| tile.is_none()));
+        assert_eq!(map.iter().count(), MAP_WIDTH * MAP_HEIGHT);
+    }
+
+    #[test]
+    fn test_map_blit() {
+        let glyph = Glyph::default();
+        let color = Color::default();
+        let tile = Tile::new(&glyph, &color);
+
+        let mut chunk = Map::default();
+        chunk.set_tile(Coord::new(0, 0), tile).unwrap();
+        chunk.set_tile(Coord::new(2, 1), tile).unwrap();
+
+        let mut map = Map::default();
+        map.blit(Coord::new(100, 50), &chunk);
+
+        assert_eq!(map.get_tile(Coord::new(100, 50)).unwrap(), Some(tile));
+        assert_eq!(map.get_tile(Coord::new(102, 51)).unwrap(), Some(tile));
+        assert_eq!(map.iter().filter(|t| t.is_some()).count(), 2);
+    }
+
+    #[test]
+    fn test_map_blit_clipped() {
+        let glyph = Glyph::default();
+        let color = Color::default();
+        let tile = Tile::new(&glyph, &color);
+
+        let mut chunk = Map::default();
+        chunk.set_tile(Coord::new(0, 0), tile).unwrap();
+        chunk.set_tile(Coord::new(1, 0), tile).unwrap();
+
+        let mut map = Map::default();
+        map.blit(Coord::new(map.width() - 1, 0), &chunk);
+
+        assert_eq!(
+            map.get_tile(Coord::new(map.width() - 1, 0)).unwrap(),
+            Some(tile)
+        );
+        assert_eq!(map.iter().filter(|t| t.is_some()).count(), 1);
+    }
+
+    #[test]
+    fn test_map_stamp_invalid_coord() {
+        let glyph = Glyph::default();
+        let color = Color::default();
+        let tile = Tile::new(&glyph, &color);
+
+        let mut map = Map::default();
+        let brush = [(Coord::new(0, 0), tile), (Coord::new(1, 0), tile)];
+        let origin = Coord::new(MAP_WIDTH - 1, 0);
+
+        let result = map.stamp(origin, &brush);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::InvalidCoord { coord: c, size: s } if c == Coord::new(MAP_WIDTH, 0) && s == map.size()
+        );
+        assert!(map.iter().all(|t| t.is_none()));
+    }
+
+    fn walled_map<'tile>(wall: Tile<'tile>) -> Map<'tile> {
+        let mut map = Map::default();
+        for y in 0..MAP_HEIGHT {
+            map.set_tile(Coord::new(10, y), wall).unwrap();
+        }
+        for x in 0..10 {
+            map.set_tile(Coord::new(x, 5), wall).unwrap();
+        }
+
+        map
+    }
+
+    #[test]
+    fn test_map_flood_fill() {
+        let glyph = Glyph::default();
+        let wall_color = Color::new(255, 255, 255);
+        let fill_color = Color::new(255, 0, 0);
+        let wall = Tile::new(&glyph, &wall_color);
+        let fill = Tile::new(&glyph, &fill_color);
+
+        let mut map = walled_map(wall);
+        assert!(map.flood_fill(Coord::new(2, 2), fill).is_ok());
+
+        assert_eq!(map.get_tile(Coord::new(0, 0)).unwrap(), Some(fill));
+        assert_eq!(map.get_tile(Coord::new(9, 4)).unwrap(), Some(fill));
+        assert_eq!(map.get_tile(Coord::new(10, 2)).unwrap(), Some(wall));
+        assert_eq!(map.get_tile(Coord::new(2, 6)).unwrap(), None);
+        assert_eq!(map.get_tile(Coord::new(11, 2)).unwrap(), None);
+        assert_eq!(map.iter().filter(|t| **t == Some(fill)).count(), 50);
+    }
+
+    #[test]
+    fn test_map_flood_fill_steps() {
+        let glyph = Glyph::default();
+        let wall_color = Color::new(255, 255, 255);
+        let fill_color = Color::new(255, 0, 0);
+        let wall = Tile::new(&glyph, &wall_color);
+        let fill = Tile::new(&glyph, &fill_color);
+
+        let mut expected = walled_map(wall);
+        expected.flood_fill(Coord::new(20, 20), fill).unwrap();
+
+        let mut map = walled_map(wall);
+        let result = map.flood_fill_steps(Coord::new(20, 20), fill, 100);
+        assert!(result.is_ok());
+        let mut progress = result.unwrap();
+        assert!(!progress.is_done());
+        assert!(!progress.frontier().is_empty());
+
+        let mut calls = 1;
+        while !map.resume_flood_fill(&mut progress, 100) {
+            calls += 1;
+        }
+
+        assert!(calls > 1);
+        assert!(progress.is_done());
+        assert_eq!(map.tiles, expected.tiles);
+    }
+
+    #[test]
+    fn test_map_flood_fill_same_tile() {
+        let glyph = Glyph::default();
+        let color = Color::default();
+        let tile = Tile::new(&glyph, &color);
+
+        let mut map = walled_map(tile);
+        let progress = map.flood_fill_steps(Coord::new(10, 0), tile, 1).unwrap();
+
+        assert!(progress.is_done());
+        assert_eq!(map.get_tile(Coord::new(0, 0)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_map_flood_fill_invalid_coord() {
+        let glyph = Glyph::default();
+        let color = Color::default();
+        let tile = Tile::new(&glyph, &color);
+
+        let mut map = Map::default();
+        let result = map.flood_fill(Coord::new(320, 0), tile);
+
+        assert!(result.is_err());
+        assert_matches!(result.unwrap_err(), CommonError::InvalidCoord { .. });
+    }
+
+    #[test]
+    fn test_map_flipped_horizontal() {
+        let glyph = Glyph::default();
+        let color_1 = Color::new(255, 0, 0);
+        let color_2 = Color::new(0, 255, 0);
+        let tile_1 = Tile::new(&glyph, &color_1);
+        let tile_2 = Tile::new(&glyph, &color_2);
+
+        let mut map = Map::default();
+        map.set_tile(Coord::new(0, 0), tile_1).unwrap();
+        map.set_tile(Coord::new(1, 0), tile_1).unwrap();
+        map.set_tile(Coord::new(5, 3), tile_2).unwrap();
+
+        let flipped = map.flipped_horizontal();
+
+        assert_eq!(flipped.get_tile(Coord::new(319, 0)).unwrap(), Some(tile_1));
+        assert_eq!(flipped.get_tile(Coord::new(318, 0)).unwrap(), Some(tile_1));
+        assert_eq!(flipped.get_tile(Coord::new(314, 3)).unwrap(), Some(tile_2));
+        assert_eq!(flipped.get_tile(Coord::new(0, 0)).unwrap(), None);
+        assert_eq!(flipped.iter_set().count(), 3);
+        assert_eq!(flipped.flipped_horizontal().tiles, map.tiles);
+    }
+
+    #[test]
+    fn test_map_rotated_cw() {
+        let glyph = Glyph::default();
+        let color_1 = Color::new(255, 0, 0);
+        let color_2 = Color::new(0, 255, 0);
+        let tile_1 = Tile::new(&glyph, &color_1);
+        let tile_2 = Tile::new(&glyph, &color_2);
+
+        let mut map = Map::default();
+        map.set_tile(Coord::new(0, 0), tile_1).unwrap();
+        map.set_tile(Coord::new(2, 1), tile_2).unwrap();
+        map.set_tile(Coord::new(200, 0), tile_2).unwrap();
+
+        let rotated = map.rotated_cw();
+
+        assert_eq!(rotated.get_tile(Coord::new(191, 0)).unwrap(), Some(tile_1));
+        assert_eq!(rotated.get_tile(Coord::new(190, 2)).unwrap(), Some(tile_2));
+        assert_eq!(rotated.iter_set().count(), 2);
+
+        let full_turn = rotated.rotated_cw().rotated_cw().rotated_cw();
+        assert_eq!(full_turn.get_tile(Coord::new(0, 0)).unwrap(), Some(tile_1));
+        assert_eq!(full_turn.get_tile(Coord::new(2, 1)).unwrap(), Some(tile_2));
+    }
+
+    #[test]
+    fn test_map_visible_tiles() {
+        let glyph = Glyph::default();
+        let color = Color::default();
+        let tile = Tile::new(&glyph, &color);
+
+        let mut map = Map::default();
+        for coord in [
+            Coord::new(10, 5),
+            Coord::new(13, 7),
+            Coord::new(9, 5),
+            Coord::new(14, 5),
+            Coord::new(10, 8),
+        ] {
+            map.set_tile(coord, tile).unwrap();
+        }
+
+        let coords: Vec<Coord> = map
+            .visible_tiles(Coord::new(10, 5), Size::new(4, 3))
+            .map(|(coord, _)| coord)
+            .collect();
+        assert_eq!(coords, vec![Coord::new(0, 0), Coord::new(3, 2)]);
+    }
+
+    #[test]
+    fn test_map_visible_tiles_clipped() {
+        let glyph = Glyph::default();
+        let color = Color::default();
+        let tile = Tile::new(&glyph, &color);
+
+        let mut map = Map::default();
+        map.set_tile(Coord::new(MAP_WIDTH - 1, MAP_HEIGHT - 1), tile)
+            .unwrap();
+
+        let camera = Coord::new(MAP_WIDTH - 2, MAP_HEIGHT - 2);
+        let coords: Vec<Coord> = map
+            .visible_tiles(camera, Size::new(usize::MAX, usize::MAX))
+            .map(|(coord, _)| coord)
+            .collect();
+        assert_eq!(coords, vec![Coord::new(1, 1)]);
+
+        let camera = Coord::new(MAP_WIDTH, 0);
+        assert_eq!(map.visible_tiles(camera, Size::new(4, 4)).count(), 0);
+    }
+
+    #[test]
+    fn test_map_find() {
+        let glyph = Glyph::default();
+        let spawn = Glyph::default();
+        let color = Color::default();
+
+        let mut map = Map::default();
+        let coord = Coord::new(3, 4);
+        map.set_tile(Coord::new(1, 1), Tile::new(&glyph, &color))
+            .unwrap();
+        map.set_tile(coord, Tile::new(&spawn, &color)).unwrap();
+
+        let result = map.find(|_, tile| std::ptr::eq(tile.glyph, &spawn));
+        assert!(result.is_some());
+
+        let (found_coord, found_tile) = result.unwrap();
+        assert_eq!(found_coord, coord);
+        assert!(std::ptr::eq(found_tile.glyph, &spawn));
+    }
+
+    #[test]
+    fn test_map_find_none() {
+        let map = Map::default();
+
+        let result = map.find(|_, _| true);
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_map_palette_histogram() {
+        let glyph = Glyph::default();
+        let red = Color::new(255, 0, 0);
+        let blue = Color::new(0, 0, 255);
+        let unknown = Color::new(1, 2, 3);
+
+        let mut palette = Palette::default();
+        palette.set_color(1, red).unwrap();
+        palette.set_color(2, blue).unwrap();
+
+        let mut map = Map::default();
+        map.set_tile(Coord::new(0, 0), Tile::new(&glyph, &red))
+            .unwrap();
+        map.set_tile(Coord::new(1, 0), Tile::new(&glyph, &red))
+            .unwrap();
+        map.set_tile(Coord::new(2, 0), Tile::new(&glyph, &blue))
+            .unwrap();
+        map.set_tile(Coord::new(3, 0), Tile::new(&glyph, &unknown))
+            .unwrap();
+
+        let histogram = map.palette_histogram(&palette);
+        assert_eq!(histogram.len(), palette.lenght());
+        assert_eq!(histogram[0], 0);
+        assert_eq!(histogram[1], 2);
+        assert_eq!(histogram[2], 1);
+        assert_eq!(histogram.iter().sum::<usize>(), 3);
+    }
+
+    #[test]
+    fn test_map_to_ascii() {
+        let wall = Glyph::default();
+        let floor = Glyph::default();
+        let color = Color::default();
+
+        let mut map = Map::default();
+        map.set_tile(Coord::new(0, 0), Tile::new(&wall, &color))
+            .unwrap();
+        map.set_tile(Coord::new(1, 0), Tile::new(&wall, &color))
+            .unwrap();
+        map.set_tile(Coord::new(1, 1), Tile::new(&floor, &color))
+            .unwrap();
+
+        let ascii = map.to_ascii(|tile| {
+            if std::ptr::eq(tile.glyph, &wall) {
+                '#'
+            } else {
+                '.'
+            }
+        });
+
+        let lines: Vec<&str> = ascii.split('\n').collect();
+        assert_eq!(lines.len(), MAP_HEIGHT);
+        assert!(lines.iter().all(|line| line.chars().count() == MAP_WIDTH));
+        assert_eq!(&lines[0][..3], "## ");
+        assert_eq!(&lines[1][..3], " . ");
+        assert_eq!(lines[2].trim(), "");
+    }
+
     #[test]
     fn test_map_coords() {
         let map = Map::default();
@@ -256,10 +1049,10 @@ mod tests {
             assert_eq!(coord.x, x);
             assert_eq!(coord.y, y);
 
-            y += 1;
-            if y == map.width() {
-                y = 0;
-                x += 1;
+            x += 1;
+            if x == map.width() {
+                x = 0;
+                y += 1;
             }
         }
     }
@@ -273,6 +1066,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_map_rows() {
+        let glyph = Glyph::default();
+        let color = Color::default();
+        let tile = Tile::new(&glyph, &color);
+
+        let mut map = Map::default();
+        map.set_tile(Coord::new(5, 3), tile).unwrap();
+
+        let rows: Vec<&[Option<Tile>]> = map.rows().collect();
+        assert_eq!(rows.len(), MAP_HEIGHT);
+        assert!(rows.iter().all(|row| row.len() == MAP_WIDTH));
+        assert_eq!(rows[3][5], Some(tile));
+        assert_eq!(
+            rows.iter()
+                .flat_map(|row| row.iter())
+                .filter(|t| t.is_some())
+                .count(),
+            1
+        );
+    }
+
     #[test]
     fn test_map_iter_mut() {
         let glyph = Glyph::default();
@@ -324,4 +1139,102 @@ mod tests {
             assert_eq!(tile.unwrap(), new_tile);
         }
     }
+
+    #[test]
+    fn test_map_iter_set() {
+        let glyph = Glyph::default();
+        let color = Color::default();
+        let tile = Tile::new(&glyph, &color);
+
+        let mut map = Map::default();
+        map.set_tile(Coord::new(7, 0), tile).unwrap();
+        map.set_tile(Coord::new(2, 5), tile).unwrap();
+
+        let coords: Vec<Coord> = map.iter_set().map(|(coord, _)| coord).collect();
+        assert_eq!(coords, vec![Coord::new(7, 0), Coord::new(2, 5)]);
+    }
+
+    #[test]
+    fn test_map_iter_mut_set() {
+        let glyph = Glyph::default();
+        let color = Color::default();
+        let new_color = Color::new(255, 0, 0);
+
+        let mut map = Map::default();
+        let set_coords = [Coord::new(0, 0), Coord::new(10, 3), Coord::new(319, 191)];
+        for coord in set_coords {
+            map.set_tile(coord, Tile::new(&glyph, &color)).unwrap();
+        }
+
+        let mut count = 0;
+        for (coord, tile) in map.iter_mut_set() {
+            assert!(set_coords.contains(&coord));
+            tile.color = &new_color;
+            count += 1;
+        }
+
+        assert_eq!(count, 3);
+        for (coord, tile) in map.enumerate() {
+            if set_coords.contains(&coord) {
+                assert_eq!(*tile, Some(Tile::new(&glyph, &new_color)));
+            } else {
+                assert!(tile.is_none());
+            }
+        }
+    }
+
+    #[test]
+    fn test_map_to_owned_indices() {
+        let mut font = Font::default();
+        font.glyphs[65]
+            .set_pixel(Coord::new(0, 0), GlyphPixel::Solid)
+            .unwrap();
+        let mut palette = Palette::default();
+        palette.set_color(3, Color::new(255, 0, 0)).unwrap();
+
+        let mut map = Map::default();
+        map.set_tile(
+            Coord::new(4, 2),
+            Tile::new(&font.glyphs[65], &palette.colors[3]),
+        )
+        .unwrap();
+        map.set_tile(
+            Coord::new(0, 0),
+            Tile::new(&font.glyphs[10], &palette.colors[0]),
+        )
+        .unwrap();
+
+        let result = map.to_owned_indices(&font, &palette);
+        assert!(result.is_ok());
+
+        let index_map = result.unwrap();
+        assert_eq!(
+            index_map.get_tile(Coord::new(4, 2)).unwrap(),
+            Some(IndexTile::new(65, 3))
+        );
+        assert_eq!(
+            index_map.get_tile(Coord::new(0, 0)).unwrap(),
+            Some(IndexTile::new(10, 0))
+        );
+        assert_eq!(index_map.iter().filter(|t| t.is_some()).count(), 2);
+    }
+
+    #[test]
+    fn test_map_to_owned_indices_unresolved() {
+        let font = Font::default();
+        let palette = Palette::default();
+        let glyph = Glyph::default();
+        let color = Color::new(1, 2, 3);
+
+        let mut map = Map::default();
+        map.set_tile(Coord::new(1, 1), Tile::new(&glyph, &color))
+            .unwrap();
+
+        let result = map.to_owned_indices(&font, &palette);
+        assert!(result.is_err());
+        assert_matches!(
+            result.unwrap_err(),
+            CommonError::UnresolvedTile { coord: c } if c == Coord::new(1, 1)
+        );
+    }
 }